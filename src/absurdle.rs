@@ -0,0 +1,250 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::char::{letters, normalize, CharStatus, Letter};
+use crate::dictionary::Dictionary;
+use crate::difficulty::{self, Difficulty};
+use crate::feedback::Feedback;
+use crate::pattern::{self, Pattern};
+use crate::session::{suggest, validate_word, Command, Event, InvalidGuess, Session};
+use crate::solver::Solver;
+
+/// The colors the game gives a guess: the ones that keep the most candidates. Between groups as big,
+/// the one that reveals the least, with fewer correct letters and then fewer misplaced ones
+fn dodge(partition: &HashMap<Pattern, usize>, length: usize) -> Pattern {
+    partition
+        .iter()
+        .max_by_key(|(&pattern, &size)| {
+            let statuses = pattern::decode(pattern, length);
+            let count = |status: CharStatus| statuses.iter().filter(|s| **s == status).count();
+            (
+                size,
+                Reverse(count(CharStatus::Correct)),
+                Reverse(count(CharStatus::Misplaced)),
+                Reverse(pattern),
+            )
+        })
+        .map(|(&pattern, _)| pattern)
+        .expect("there should always be a candidate left")
+}
+
+/// A game without a secret word, like Absurdle: every guess gets the colors that leave the most secret
+/// words possible, so the player has to corner the game until a single word is left and then guess it.
+/// There is no limit of tries. Like [`crate::GameSession`], it does not read nor print anything
+pub struct AbsurdleSession<'a> {
+    dictionary: &'a Dictionary,
+    /// Keeps the secret words consistent with every row, the candidates
+    solver: Solver<'a>,
+    /// The guesses scored against one of the candidates, all of them give the same colors
+    feedback: Feedback,
+    guesses: Vec<String>,
+    length: usize,
+    difficulty: Difficulty,
+    /// Candidates left after each guess
+    alive: Vec<usize>,
+    quit: bool,
+}
+
+impl<'a> AbsurdleSession<'a> {
+    /// Every secret word with the length is a candidate at first, none if there are no secret words that long
+    pub fn new(dictionary: &'a Dictionary, length: usize) -> Option<Self> {
+        // Checked first, so no table is built for a length without secret words
+        let secret = letters(dictionary.secret_words(length).first()?);
+        let solver = Solver::new(dictionary, length)?;
+        Some(Self {
+            dictionary,
+            solver,
+            feedback: Feedback::new(
+                &secret,
+                &dictionary.abecedary,
+                &dictionary.folding,
+                &dictionary.alphabet,
+            ),
+            guesses: Vec::new(),
+            length,
+            difficulty: Difficulty::Normal,
+            alive: Vec::new(),
+            quit: false,
+        })
+    }
+
+    /// Sets how much the hints revealed restrict the next guesses
+    pub fn with_difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
+    /// Scores every guess again against the first candidate left. Every candidate gives the guesses the
+    /// colors they already got, so the rows stay the same and the last one is scored too
+    fn rescore(&mut self) {
+        let secret: Vec<Letter> = letters(&self.solver.candidates()[0]);
+        let dictionary = self.dictionary;
+        self.feedback = Feedback::new(
+            &secret,
+            &dictionary.abecedary,
+            &dictionary.folding,
+            &dictionary.alphabet,
+        );
+        for guess in self.guesses.iter() {
+            self.feedback.compare(guess.clone());
+        }
+    }
+
+    /// Checks the guess could be played, without scoring it
+    pub fn validate(&self, guess: &str) -> Result<(), InvalidGuess> {
+        validate_word(self.dictionary, self.length, guess)?;
+        let guess = letters(guess);
+        difficulty::check(
+            self.difficulty,
+            self.feedback.get_history(),
+            &guess,
+            &self.dictionary.folding,
+        )
+        .map_err(InvalidGuess::BrokenConstraint)
+    }
+
+    // Getters
+
+    /// The words the game is played with
+    pub fn dictionary(&self) -> &'a Dictionary {
+        self.dictionary
+    }
+
+    /// The guesses scored against one of the candidates left, all of them give the same colors
+    pub fn feedback(&self) -> &Feedback {
+        &self.feedback
+    }
+
+    /// Letters of the secret word
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Guesses scored so far
+    pub fn tries(&self) -> usize {
+        self.guesses.len()
+    }
+
+    /// How much the hints revealed restrict the next guesses
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Secret words still possible
+    pub fn candidates(&self) -> usize {
+        self.solver.candidate_indexes().len()
+    }
+
+    /// How many secret words were still possible after each guess
+    pub fn alive(&self) -> &[usize] {
+        &self.alive
+    }
+
+    /// Checks if the last candidate was guessed
+    pub fn is_won(&self) -> bool {
+        self.feedback.win
+    }
+
+    /// Checks if the game is won or quit
+    pub fn is_over(&self) -> bool {
+        self.quit || self.is_won()
+    }
+}
+
+impl Session for AbsurdleSession<'_> {
+    type Event = Event;
+
+    fn command(&mut self, command: Command) -> Self::Event {
+        match command {
+            Command::PreviousWord => {
+                let guess = self.feedback.get_guess();
+                Event::PreviousWord((!guess.is_empty()).then(|| guess.to_vec()))
+            }
+            Command::History => Event::History(self.feedback.get_history().clone()),
+            Command::Abecedary => Event::Abecedary(self.feedback.get_abecedary()),
+            // There is no limit of tries, what is left to corner are the candidates
+            Command::TriesLeft => Event::Alive(self.candidates()),
+            Command::Suggest => {
+                Event::Suggestions(suggest(self.dictionary, self.length, self.difficulty, &self.feedback))
+            }
+            Command::Statistics => Event::Statistics,
+            Command::Help => Event::Help,
+            Command::Quit => {
+                self.quit = true;
+                Event::Quit
+            }
+        }
+    }
+
+    /// Gives the guess the colors that keep the most candidates. A scored guess is followed by a win or
+    /// the candidates left
+    fn guess(&mut self, guess: &str) -> Vec<Self::Event> {
+        if self.is_over() {
+            return vec![Event::InvalidGuess(InvalidGuess::GameOver)];
+        }
+        if let Err(reason) = self.validate(guess) {
+            return vec![Event::InvalidGuess(reason)];
+        }
+
+        let guess = normalize(guess);
+        let guess_letters = letters(&guess);
+        let pattern = dodge(&self.solver.partition(&guess_letters), self.length);
+        self.solver
+            .update(&guess_letters, &pattern::decode(pattern, self.length));
+        self.guesses.push(guess);
+        self.alive.push(self.candidates());
+        self.rescore();
+
+        let mut events = vec![Event::Scored(self.feedback.get_guess().to_vec())];
+        events.push(if self.is_won() {
+            Event::Win {
+                secret: self.feedback.get_secret(),
+            }
+        } else {
+            Event::Alive(self.candidates())
+        });
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii::Folding;
+    use crate::solver::parse_pattern;
+
+    /// The colors the game gives "crane" when the secret words are the ones given
+    fn dodged(secret_words: &str) -> Vec<CharStatus> {
+        let dictionary = Dictionary::new(b"crane\n", secret_words.as_bytes(), Folding::default());
+        let solver = Solver::new(&dictionary, 5).unwrap();
+        pattern::decode(dodge(&solver.partition(&letters("crane")), 5), 5)
+    }
+
+    #[test]
+    fn the_largest_group_is_kept_even_if_it_reveals_more() {
+        // "cloud" and "chimp" get a correct C, "dusty" gets nothing
+        assert_eq!(dodged("cloud\nchimp\ndusty\n"), parse_pattern("g....").unwrap());
+    }
+
+    #[test]
+    fn between_groups_as_big_the_one_with_fewer_correct_letters_is_kept() {
+        // "acorn" gets four misplaced letters, "cloud" a single correct one
+        assert_eq!(dodged("cloud\nacorn\n"), parse_pattern("yyyy.").unwrap());
+    }
+
+    #[test]
+    fn between_groups_as_big_and_as_correct_the_one_with_fewer_misplaced_letters_is_kept() {
+        // Both get a correct C, but "chirp" also gets a misplaced R
+        assert_eq!(dodged("chirp\ncloud\n"), parse_pattern("g....").unwrap());
+        assert_eq!(dodged("cloud\nchirp\n"), parse_pattern("g....").unwrap());
+    }
+
+    #[test]
+    fn guesses_dodge_the_secret_words() {
+        let dictionary = Dictionary::new(b"crane\n", b"cloud\nchimp\ndusty\n", Folding::default());
+        let mut session = AbsurdleSession::new(&dictionary, 5).unwrap();
+        let events = session.input("crane");
+        assert_eq!(events[1], Event::Alive(2));
+        assert_eq!(session.candidates(), 2);
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use crate::ascii::{self, Folding};
+use crate::char::{letters, Char, CharStatus, Letter};
+
+/// Rows of the keyboard used when a language does not have its own
+const QWERTY: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// How the letters of a language are sorted and where they are on its keyboard
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alphabet {
+    /// Position of each letter in the alphabet, letters not in it go after all the ones that are
+    order: HashMap<Letter, usize>,
+    keyboard: Vec<Vec<Letter>>,
+}
+
+impl Default for Alphabet {
+    /// No order of its own, letters go after their base letter, and a QWERTY keyboard
+    fn default() -> Self {
+        Self::new("", &[])
+    }
+}
+
+/// Letters of the text, skipping the spaces that separate them
+fn split_letters(text: &str) -> Vec<Letter> {
+    letters(text)
+        .into_iter()
+        .filter(|letter| !letter.trim().is_empty())
+        .collect()
+}
+
+impl Alphabet {
+    /// Takes the letters in alphabetical order, like "a á b c", and the rows of the keyboard, like "qwertyuiop".
+    /// Without rows the keyboard is QWERTY
+    pub fn new(alphabet: &str, keyboard: &[&str]) -> Self {
+        let keyboard = if keyboard.is_empty() { &QWERTY[..] } else { keyboard };
+        Self {
+            order: split_letters(alphabet)
+                .into_iter()
+                .enumerate()
+                .map(|(i, letter)| (letter, i))
+                .collect(),
+            keyboard: keyboard.iter().map(|row| split_letters(row)).collect(),
+        }
+    }
+
+    /// Key to sort letters in the order of the alphabet
+    pub fn sort_key(&self, letter: &str) -> (usize, (String, bool, String)) {
+        (
+            self.order.get(letter).copied().unwrap_or(usize::MAX),
+            ascii::sort_key(letter),
+        )
+    }
+
+    /// Sorts the chars in the order of the alphabet
+    pub fn sort(&self, chars: &mut [Char]) {
+        chars.sort_by_cached_key(|c| self.sort_key(&c.character));
+    }
+
+    /// The abecedary laid out as the keyboard, like the on screen keyboard of the real game.
+    /// Letters that are not on the keyboard, like "á", color the key of the letter they fold to
+    pub fn keyboard(&self, abecedary: &[Char], folding: &Folding) -> Vec<Vec<Char>> {
+        self.keyboard
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|key| {
+                        let folded = folding.fold_str(key);
+                        let mut key = Char::new(key.clone());
+                        let best = abecedary
+                            .iter()
+                            .filter(|c| c.is_revealed() && folding.fold_str(&c.character) == folded)
+                            .max_by_key(|c| match c.status {
+                                CharStatus::Correct => 2,
+                                CharStatus::Misplaced => 1,
+                                CharStatus::Incorrect => 0,
+                            });
+                        if let Some(best) = best {
+                            key.set_status(best.status.clone());
+                        }
+                        key
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(alphabet: &Alphabet, word: &str) -> Vec<Letter> {
+        let mut chars: Vec<Char> = letters(word).into_iter().map(Char::new).collect();
+        alphabet.sort(&mut chars);
+        chars.into_iter().map(|c| c.character).collect()
+    }
+
+    fn revealed(letter: &str, status: CharStatus) -> Char {
+        let mut c = Char::new(letter.to_owned());
+        c.set_status(status);
+        c
+    }
+
+    #[test]
+    fn letters_follow_the_order_of_the_alphabet() {
+        let spanish = Alphabet::new("a á b c d e é f g h i í j k l m n ñ o ó p q r s t u ú ü v w x y z", &[]);
+        assert_eq!(sorted(&spanish, "zñoáanb"), ["a", "á", "b", "n", "ñ", "o", "z"]);
+    }
+
+    #[test]
+    fn letters_not_in_the_alphabet_go_after_it_next_to_their_base_letter() {
+        let alphabet = Alphabet::new("a b c", &[]);
+        assert_eq!(sorted(&alphabet, "öcoáb"), ["b", "c", "á", "o", "ö"]);
+        assert_eq!(sorted(&Alphabet::default(), "zñoáan"), ["a", "á", "n", "ñ", "o", "z"]);
+    }
+
+    #[test]
+    fn the_keyboard_has_the_rows_of_the_language() {
+        let german = Alphabet::new("", &["qwertzuiopüß", "asdfghjklöä", "yxcvbnm"]);
+        let rows = german.keyboard(&[], &Folding::default());
+        let row: Vec<&str> = rows[0].iter().map(|c| c.character.as_str()).collect();
+        assert_eq!(row, ["q", "w", "e", "r", "t", "z", "u", "i", "o", "p", "ü", "ß"]);
+        assert_eq!(rows[2].len(), 7);
+        assert!(rows.iter().flatten().all(|key| !key.is_revealed()));
+    }
+
+    #[test]
+    fn letters_off_the_keyboard_color_the_key_they_fold_to_with_their_best_status() {
+        let abecedary = [
+            revealed("a", CharStatus::Incorrect),
+            revealed("á", CharStatus::Misplaced),
+            revealed("e", CharStatus::Correct),
+        ];
+        let rows = Alphabet::default().keyboard(&abecedary, &Folding::default());
+        let key = |letter: &str| {
+            rows.iter()
+                .flatten()
+                .find(|key| key.character == letter)
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(key("a").status, CharStatus::Misplaced);
+        assert_eq!(key("e").status, CharStatus::Correct);
+        assert!(!key("q").is_revealed());
+    }
+}
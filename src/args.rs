@@ -1,42 +1,142 @@
-use crate::language::{Language, LanguagePack};
-use clap::Parser;
+use crate::game;
+use another_wordle_clone::char::{letters, Letter};
+use another_wordle_clone::strategy::STRATEGIES;
+use another_wordle_clone::{daily, random};
+use another_wordle_clone::{Difficulty, Language, LanguagePack, Languages};
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-const DEFAULT_LANG: Language = Language::Spanish;
-const DEFAULT_SECRET: Option<Vec<char>> = None;
+const DEFAULT_LANG: &str = "es";
+const DEFAULT_SECRET: Option<Vec<Letter>> = None;
 const DEFAULT_TRIES: u8 = 5;
+const DEFAULT_LENGTH: usize = 5;
+const DEFAULT_BOARDS: usize = 1;
+/// Like Xordle, there are more tries to find two words
+const XORDLE_TRIES: u8 = 9;
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Show your statistics for the language and length | Muestra tus estadísticas del idioma y la longitud
+    Stats,
+    /// Play every secret word with a strategy and show how many guesses it needed | Juega todas las palabras secretas con una estrategia y muestra cuántos intentos necesitó
+    Bench {
+        /// How guesses are chosen | Cómo se eligen los intentos
+        #[arg(long = "strategy", default_value = "entropy", value_parser = STRATEGIES)]
+        strategy: String,
+        /// First guess of every game, by default the one the strategy chooses | Primer intento de cada partida, por defecto el que elige la estrategia
+        #[arg(long = "opening")]
+        opening: Option<String>,
+    },
+    /// Suggest guesses for a game played somewhere else, writing the colors each guess got | Sugiere intentos para una partida jugada en otro lugar, escribiendo los colores que recibió cada intento
+    Solve {
+        /// One color of each row is a lie, like in the --fibble mode | Un color de cada fila es mentira, como en el modo --fibble
+        #[arg(long = "fibble")]
+        fibble: bool,
+    },
+}
 
 #[derive(Debug, Parser)]
 pub struct Args {
-    /// Change the default language | Cambia el idioma por defecto ("en", "es")
-    #[arg(short = 'l', long = "language")]
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Change the default language, by its name or code | Cambia el idioma por defecto, por su nombre o código ("en", "es", "pt", "fr", "de", "it", "ru", "el")
+    #[arg(short = 'l', long = "language", global = true)]
     language: Option<String>,
     /// Set a secret word | Asigna una palabra secreta
     #[arg(short = 's', long = "secret")]
     secret: Option<String>,
-    /// Set max guesses | Asigna los intentos máximos
-    #[arg(short = 't', long = "tries")]
+    /// Set max guesses, by default there is one more for each extra board | Asigna los intentos máximos, por defecto hay uno más por cada tablero extra
+    #[arg(short = 't', long = "tries", global = true, value_parser = clap::value_parser!(u8).range(1..))]
     tries: Option<u8>,
+    /// Set the letters of the secret word | Asigna las letras de la palabra secreta
+    #[arg(short = 'n', long = "length", global = true)]
+    length: Option<usize>,
+    /// Revealed hints must be used in every guess, "strict" is even harder | Las pistas reveladas deben usarse en cada intento, "strict" es aún más difícil
+    #[arg(
+        long = "hard",
+        num_args = 0..=1,
+        default_missing_value = "hard",
+        value_name = "MODE",
+        value_parser = ["normal", "hard", "strict"]
+    )]
+    hard: Option<String>,
+    /// Play the word of the day, the same for everyone | Juega la palabra del día, la misma para todos
+    #[arg(short = 'd', long = "daily")]
+    daily: bool,
+    /// Play the word of the day of another date, like 2025-01-31 | Juega la palabra del día de otra fecha, como 2025-01-31
+    #[arg(long = "date")]
+    date: Option<String>,
+    /// Choose secret words with a seed, to replay a game | Elige las palabras secretas con una semilla, para repetir una partida
+    #[arg(long = "seed")]
+    seed: Option<u64>,
+    /// Guess many secret words at once, each one on its own board | Adivina muchas palabras secretas a la vez, cada una en su propio tablero
+    #[arg(short = 'b', long = "boards", global = true)]
+    boards: Option<usize>,
+    /// Use the words in this file, one per line, as the ones that can be guessed | Usa las palabras de este archivo, una por línea, como las que se pueden adivinar
+    #[arg(long = "dictionary", value_name = "PATH")]
+    dictionary: Option<PathBuf>,
+    /// Choose secret words from this file, one per line | Elige las palabras secretas de este archivo, una por línea
+    #[arg(long = "secrets", value_name = "PATH")]
+    secrets: Option<PathBuf>,
+    /// Accents count, a letter with an accent is not the same as the letter without it | Los acentos cuentan, una letra con tilde no es la misma que la letra sin ella
+    #[arg(long = "strict-accents")]
+    strict_accents: bool,
+    /// Show the letters of the "a" command as a keyboard | Muestra las letras del comando "a" como un teclado
+    #[arg(long = "keyboard")]
+    keyboard: bool,
+    /// Start playing without the welcome message | Empieza a jugar sin el mensaje de bienvenida
+    #[arg(long = "no-welcome")]
+    no_welcome: bool,
+    /// Play a single game and exit, without asking to play again | Juega una sola partida y sale, sin preguntar si jugar de nuevo
+    #[arg(long = "once")]
+    once: bool,
+    /// Read guesses and commands from stdin, one per line, and write each event as a json object per line, for bots and scripts. Games are not recorded in the statistics, "p" only shows them | Lee intentos y comandos de stdin, uno por línea, y escribe cada evento como un objeto json por línea, para bots y scripts. Las partidas no se guardan en las estadísticas, "p" solo las muestra
+    #[arg(long = "json")]
+    json: bool,
+    /// Play against a game that has no secret word and gives each guess the colors that leave the most words possible, until only one is left. There is no limit of tries and statistics are not recorded | Juega contra un juego sin palabra secreta que da a cada intento los colores que dejan más palabras posibles, hasta que solo queda una. No hay límite de intentos y las estadísticas no se guardan
+    #[arg(long = "absurdle", conflicts_with_all = ["secret", "daily", "date", "seed", "boards"])]
+    absurdle: bool,
+    /// Guess two secret words without letters in common on a single board, each letter gets the best color it gets with any of them. Statistics are not recorded | Adivina dos palabras secretas sin letras en común en un solo tablero, cada letra recibe el mejor color que recibe con cualquiera de ellas. Las estadísticas no se guardan
+    #[arg(long = "xordle", conflicts_with_all = ["secret", "daily", "date", "boards", "absurdle", "hard"])]
+    xordle: bool,
+    /// One letter of each row shows a wrong color, chosen with the seed. The lies are revealed when the game is over and statistics are not recorded | Una letra de cada fila muestra un color erróneo, elegida con la semilla. Las mentiras se revelan al terminar la partida y las estadísticas no se guardan
+    #[arg(long = "fibble", conflicts_with_all = ["hard", "absurdle", "xordle"])]
+    fibble: bool,
+    /// Write the result grid with plain ASCII instead of emojis | Escribe el resultado con ASCII en lugar de emojis
+    #[arg(long = "ascii")]
+    ascii: bool,
 }
 
 impl Args {
-    pub fn get_language(&self) -> Language {
+    pub fn get_command(&self) -> Option<&Command> {
+        self.command.as_ref()
+    }
+
+    pub fn get_language<'a>(&self, languages: &'a Languages) -> &'a Language {
         if let Some(language_str) = &self.language {
-            if let Ok(language) = Language::from_str(language_str) {
+            if let Some(language) = languages.find(language_str) {
                 return language;
             }
         }
-        DEFAULT_LANG
+        languages
+            .find(DEFAULT_LANG)
+            .expect("default language should be bundled")
     }
 
-    pub fn get_secret(&self, abecedary: &mut std::collections::HashSet<char>, lang: &LanguagePack) -> Option<Vec<char>> {
-        let secret_vec: Vec<char> = if let Some(secret) = &self.secret {
-            secret.chars().collect()
+    pub fn get_secret(
+        &self,
+        abecedary: &mut std::collections::HashSet<Letter>,
+        lang: &LanguagePack,
+    ) -> Option<Vec<Letter>> {
+        let secret_vec: Vec<Letter> = if let Some(secret) = &self.secret {
+            letters(secret)
         } else {
             return DEFAULT_SECRET;
         };
 
-        if secret_vec.len() == 5 {
+        if secret_vec.len() == self.get_length() {
             for c in secret_vec.iter() {
                 abecedary.insert(c.clone());
             }
@@ -50,8 +150,108 @@ impl Args {
     pub fn get_tries(&self) -> u8 {
         if let Some(tries) = self.tries {
             tries
-        } else {
+        } else if self.xordle {
+            XORDLE_TRIES
+        } else if self.get_boards() == 1 {
             DEFAULT_TRIES
+        } else {
+            // Like Dordle, Quordle and Octordle: 7 tries for 2 boards, 9 for 4 and 13 for 8
+            let boards = u8::try_from(self.get_boards()).unwrap_or(u8::MAX);
+            DEFAULT_TRIES.saturating_add(boards)
+        }
+    }
+
+    pub fn get_boards(&self) -> usize {
+        if let Some(boards) = self.boards {
+            boards.max(1)
+        } else {
+            DEFAULT_BOARDS
+        }
+    }
+
+    pub fn get_length(&self) -> usize {
+        if let Some(length) = self.length {
+            length
+        } else {
+            DEFAULT_LENGTH
+        }
+    }
+
+    pub fn get_difficulty(&self) -> Difficulty {
+        self.hard.as_deref().map_or(Difficulty::Normal, |difficulty| {
+            Difficulty::from_str(difficulty).expect("difficulties are checked by clap")
+        })
+    }
+
+    /// Date of the daily word to play, none if not playing a daily word
+    pub fn get_date(&self, lang: &LanguagePack) -> Option<NaiveDate> {
+        if let Some(date_str) = &self.date {
+            if let Some(date) = daily::parse_date(date_str) {
+                return Some(date);
+            }
+            eprintln!("{}", lang.args_invalid_date);
+            return Some(daily::today());
+        }
+        if self.daily {
+            Some(daily::today())
+        } else {
+            None
         }
     }
+
+    /// Seed to choose the first random secret word, random itself if none was given
+    pub fn get_seed(&self) -> u64 {
+        if let Some(seed) = self.seed {
+            seed
+        } else {
+            random::random_seed()
+        }
+    }
+
+    /// How the game with that seed is played, the seed also chooses the lies with --fibble
+    pub fn get_options(&self, seed: u64) -> game::Options {
+        game::Options {
+            max_tries: self.get_tries(),
+            difficulty: self.get_difficulty(),
+            ascii: self.ascii,
+            keyboard: self.keyboard,
+            lies: self.fibble.then_some(seed),
+        }
+    }
+
+    pub fn get_no_welcome(&self) -> bool {
+        self.no_welcome
+    }
+
+    pub fn get_once(&self) -> bool {
+        self.once
+    }
+
+    pub fn get_json(&self) -> bool {
+        self.json
+    }
+
+    pub fn get_xordle(&self) -> bool {
+        self.xordle
+    }
+
+    pub fn get_absurdle(&self) -> bool {
+        self.absurdle
+    }
+
+    pub fn get_fibble(&self) -> bool {
+        self.fibble
+    }
+
+    pub fn get_strict_accents(&self) -> bool {
+        self.strict_accents
+    }
+
+    pub fn get_dictionary_path(&self) -> Option<&Path> {
+        self.dictionary.as_deref()
+    }
+
+    pub fn get_secrets_path(&self) -> Option<&Path> {
+        self.secrets.as_deref()
+    }
 }
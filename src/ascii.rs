@@ -1,28 +1,170 @@
-pub fn has_non_ascii(s: &str) -> bool {
-    s.contains('á')
-        || s.contains('é')
-        || s.contains('í')
-        || s.contains('ó')
-        || s.contains('ú')
-        || s.contains('ü')
+use std::collections::{HashMap, HashSet};
+
+use unicode_normalization::char::{decompose_canonical, is_combining_mark};
+
+/// Letters that are taken as another one, like "á" as "a", so players don't need to write accents.
+/// Any letter with diacritics is taken as its base letter, except the ones its language keeps as
+/// letters of their own, like "ñ" in spanish. Each language has its own, read from its folding file
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Folding {
+    /// Letters that are never folded
+    keep: HashSet<char>,
+    /// Letters folded to another one that is not their base letter, or that have no decomposition
+    table: HashMap<char, char>,
+    /// No letter is folded, accents count and "á" is not "a"
+    strict: bool,
+}
+
+/// A line of a folding file that is neither a letter nor two letters
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FoldingError {
+    /// Number of the line, starting at 1
+    pub line: usize,
+    /// The line as it was written, without the spaces around it
+    pub text: String,
+}
+
+impl std::fmt::Display for FoldingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: \"{}\" must be a letter or two letters",
+            self.line, self.text
+        )
+    }
+}
+
+/// The letter without its diacritics, none if it has none or if it does not decompose to a single letter
+fn base_letter(c: char) -> Option<char> {
+    let mut decomposed = Vec::new();
+    decompose_canonical(c, |d| decomposed.push(d));
+    match decomposed.split_first() {
+        Some((&base, marks)) if !marks.is_empty() && marks.iter().all(|&m| is_combining_mark(m)) => Some(base),
+        _ => None,
+    }
 }
 
-pub fn asciify(c: char) -> char {
-    match c {
-        'á' => 'a',
-        'é' => 'e',
-        'í' => 'i',
-        'ó' => 'o',
-        'ú' | 'ü' => 'u',
-        default => default,
+impl Folding {
+    /// Reads a folding file. A line with a single letter keeps it as a letter of its own, like "ñ",
+    /// and a line with two letters folds the first one as the second, like "ø o".
+    /// Blank lines and lines starting with "#" are skipped, any other line is an error
+    pub fn parse(text: &str) -> Result<Self, FoldingError> {
+        let mut folding = Self::default();
+        for (i, line) in text.lines().map(str::trim).enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let letters: Option<Vec<char>> = line
+                .split_whitespace()
+                .map(|s| {
+                    let mut chars = s.chars();
+                    chars.next().filter(|_| chars.next().is_none())
+                })
+                .collect();
+            match letters.as_deref() {
+                Some(&[letter, folded]) => {
+                    folding.table.insert(letter, folded);
+                }
+                Some(&[letter]) => {
+                    folding.keep.insert(letter);
+                }
+                _ => {
+                    return Err(FoldingError {
+                        line: i + 1,
+                        text: line.to_owned(),
+                    })
+                }
+            }
+        }
+        Ok(folding)
+    }
+
+    /// Folding that takes every letter as itself, for players who want accents to count
+    pub fn strict() -> Self {
+        Self {
+            strict: true,
+            ..Self::default()
+        }
+    }
+
+    /// Checks if any letter of the word is folded as another one
+    pub fn has_foldable(&self, s: &str) -> bool {
+        s.chars().any(|c| self.fold(c) != c)
+    }
+
+    /// The letter the char is taken as, itself if it is not folded
+    pub fn fold(&self, c: char) -> char {
+        if self.strict || self.keep.contains(&c) {
+            return c;
+        }
+        if let Some(folded) = self.table.get(&c) {
+            return *folded;
+        }
+        base_letter(c).map_or(c, |base| self.fold(base))
+    }
+
+    /// The text with every char folded
+    pub fn fold_str(&self, s: &str) -> String {
+        s.chars().map(|c| self.fold(c)).collect()
+    }
+
+    /// Checks if letter a is similar to letter b, both ways, so a final "ς" is the same as a "σ" wherever it's written
+    pub fn compare_letters(&self, a: &str, b: &str) -> bool {
+        a == b || self.fold_str(a) == self.fold_str(b)
     }
 }
 
-pub fn asciify_str(s: &str) -> String {
-    s.to_owned().chars().map(asciify).collect()
+/// Key to sort letters alphabetically: letters with diacritics go right after their base letter,
+/// instead of after the whole alphabet as their code points would put them, like "ñ" after "n" or "ё" after "е"
+pub fn sort_key(letter: &str) -> (String, bool, String) {
+    let base: String = letter.chars().map(|c| base_letter(c).unwrap_or(c)).collect();
+    let has_diacritics = base != letter;
+    (base, has_diacritics, letter.to_owned())
 }
 
-/// Checks if a is similar to b
-pub fn compare_chars(a: char, b: char) -> bool {
-    a == asciify(b) || a == b
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundled(file: &str) -> Folding {
+        Folding::parse(file).expect("bundled folding files should be valid")
+    }
+
+    #[test]
+    fn spanish_keeps_the_ñ_but_folds_accents() {
+        let folding = bundled(include_str!("../media/spanish/folding.txt"));
+        assert_eq!(folding.fold('ñ'), 'ñ');
+        assert_eq!(folding.fold_str("árbol"), "arbol");
+        assert_eq!(folding.fold_str("pingüino"), "pinguino");
+        assert!(!folding.compare_letters("ñ", "n"));
+        assert!(folding.compare_letters("á", "a"));
+    }
+
+    #[test]
+    fn german_folds_umlauts_and_keeps_the_ß() {
+        let folding = bundled(include_str!("../media/german/folding.txt"));
+        assert_eq!(folding.fold_str("größe"), "große");
+        assert_eq!(folding.fold('ß'), 'ß');
+        assert!(!folding.compare_letters("ß", "s"));
+    }
+
+    #[test]
+    fn two_letters_fold_the_first_as_the_second() {
+        let folding = Folding::parse("# comment\n\nø o\n").unwrap();
+        assert_eq!(folding.fold_str("søt"), "sot");
+        assert!(!folding.has_foldable("sot"));
+    }
+
+    #[test]
+    fn strict_folds_nothing() {
+        assert_eq!(Folding::strict().fold_str("árbol"), "árbol");
+    }
+
+    #[test]
+    fn malformed_lines_are_errors() {
+        for (text, line) in [("ñ\nae a", 2), ("ø o x", 1), ("\n# comment\nøo", 3)] {
+            let error = Folding::parse(text).unwrap_err();
+            assert_eq!(error.line, line, "{:?}", text);
+        }
+    }
 }
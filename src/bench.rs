@@ -0,0 +1,112 @@
+use crate::char::{letters, Letter};
+use crate::dictionary::Dictionary;
+use crate::feedback::Feedback;
+use crate::solver::Solver;
+use crate::strategy::Strategy;
+
+/// Guesses after which a game is given up, so a strategy that gets stuck can't play forever
+pub const MAX_GUESSES: u8 = 20;
+
+/// A secret word played by the benchmark
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    /// The secret word the game was played with
+    pub secret: String,
+    /// Guesses needed to find the secret word, none if it was not found
+    pub guesses: Option<u8>,
+}
+
+/// Results of playing every secret word with a strategy
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    /// The opening every game started with
+    pub opening: String,
+    /// Every secret word played, in the order of the secret words list
+    pub games: Vec<Game>,
+}
+
+impl Report {
+    /// Average guesses of the games where the secret word was found, 0 if there is none
+    pub fn average(&self) -> f64 {
+        let solved: Vec<u8> = self.games.iter().filter_map(|game| game.guesses).collect();
+        if solved.is_empty() {
+            return 0.0;
+        }
+        solved.iter().map(|&guesses| guesses as f64).sum::<f64>() / solved.len() as f64
+    }
+
+    /// Games that would have been lost with that many tries
+    pub fn failures(&self, tries: u8) -> usize {
+        self.games
+            .iter()
+            .filter(|game| game.guesses.is_none_or(|guesses| guesses > tries))
+            .count()
+    }
+
+    /// The games that needed the most guesses, the ones not solved first
+    pub fn worst(&self, count: usize) -> Vec<&Game> {
+        let mut games: Vec<&Game> = self.games.iter().collect();
+        games.sort_by_key(|game| std::cmp::Reverse(game.guesses.unwrap_or(u8::MAX)));
+        games.truncate(count);
+        games
+    }
+}
+
+/// Plays one secret word, scoring the guesses the same way a real game does
+fn play(
+    dictionary: &Dictionary,
+    mut solver: Solver,
+    strategy: &mut dyn Strategy,
+    opening: &[Letter],
+    secret: &str,
+) -> Game {
+    let mut feedback = Feedback::new(
+        &letters(secret),
+        &dictionary.abecedary,
+        &dictionary.folding,
+        &dictionary.alphabet,
+    );
+    let mut guess = opening.to_vec();
+    for tries in 1..=MAX_GUESSES {
+        feedback.compare(guess.concat());
+        if feedback.win {
+            return Game {
+                secret: secret.to_owned(),
+                guesses: Some(tries),
+            };
+        }
+        let pattern: Vec<_> = feedback.get_guess().iter().map(|c| c.status.clone()).collect();
+        solver.update(&guess, &pattern);
+        match strategy.next_guess(&solver) {
+            Some(next) => guess = next,
+            None => break,
+        }
+    }
+    Game {
+        secret: secret.to_owned(),
+        guesses: None,
+    }
+}
+
+/// Plays every secret word with the length using the strategy, starting with the opening. Without one,
+/// the first guess of the strategy is chosen once and used for every secret word
+pub fn run(dictionary: &Dictionary, length: usize, strategy: &mut dyn Strategy, opening: Option<&[Letter]>) -> Report {
+    let Some(solver) = Solver::new(dictionary, length) else {
+        return Report::default();
+    };
+    let opening = match opening {
+        Some(opening) => opening.to_vec(),
+        None => match strategy.next_guess(&solver) {
+            Some(opening) => opening,
+            None => return Report::default(),
+        },
+    };
+    Report {
+        games: dictionary
+            .secret_words(length)
+            .iter()
+            .map(|secret| play(dictionary, solver.clone(), strategy, &opening, secret))
+            .collect(),
+        opening: opening.concat(),
+    }
+}
@@ -1,32 +1,86 @@
 use colored::{ColoredString, Colorize};
+use json::JsonValue;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// A letter as the player sees it, which can take more than one char: a grapheme cluster in NFC,
+/// like "á", or a syllable like "कि" in hindi
+pub type Letter = String;
+
+/// Writes the text in NFC, so a letter written as "a" followed by a combining accent is the same as "á"
+pub fn normalize(s: &str) -> String {
+    s.nfc().collect()
+}
+
+/// The letters of the word, normalized
+pub fn letters(word: &str) -> Vec<Letter> {
+    normalize(word).graphemes(true).map(str::to_owned).collect()
+}
+
+/// Color a letter gets when a guess is compared to the secret word
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum CharStatus {
+    /// Green: the letter is in the secret word in that position
     Correct,
+    /// Yellow: the letter is in the secret word, but in another position
     Misplaced,
+    /// Red: the letter is not in the secret word, or all of its copies are already colored
     Incorrect,
 }
 
+impl CharStatus {
+    /// Name of the status in json output, like "correct"
+    pub fn code(&self) -> &'static str {
+        match self {
+            CharStatus::Correct => "correct",
+            CharStatus::Misplaced => "misplaced",
+            CharStatus::Incorrect => "incorrect",
+        }
+    }
+}
+
+/// A letter of a guess or of the abecedary with the color it got
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Char {
-    pub character: char,
+    /// The letter as it was written, in lowercase
+    pub character: Letter,
+    /// The letter in uppercase, colored after its status once it is revealed
     pub colored: ColoredString,
+    /// Incorrect until the letter is compared
     pub status: CharStatus,
 }
 
+/// The letter in uppercase, or as it is if its uppercase is not a single letter, like "ß" which would be "SS".
+/// A final "ς" is shown as "Σ", like any other sigma
+fn uppercase(letter: &str) -> String {
+    let upper = normalize(&letter.to_uppercase());
+    if upper.graphemes(true).count() == 1 {
+        upper
+    } else {
+        letter.to_owned()
+    }
+}
+
 impl Char {
-    pub fn new(c: char) -> Self {
+    /// The letter not revealed yet, without color
+    pub fn new(letter: Letter) -> Self {
         Self {
-            character: c,
-            colored: ColoredString::from(c.to_uppercase().to_string()),
+            colored: ColoredString::from(uppercase(&letter)),
+            character: letter,
             status: CharStatus::Incorrect,
         }
     }
 
+    /// Checks if the letter is not in the secret word, or is but was not revealed yet
     pub fn is_incorrect(&self) -> bool {
         self.status == CharStatus::Incorrect
     }
 
+    /// Checks if the status was set, letters of the abecedary are not revealed until they are guessed
+    pub fn is_revealed(&self) -> bool {
+        self.colored.fgcolor.is_some()
+    }
+
     /// Sets status given and colors the char to the corresponding color
     pub fn set_status(&mut self, status: CharStatus) {
         self.colored.clear_style();
@@ -37,4 +91,66 @@ impl Char {
         };
         self.status = status;
     }
+
+    /// The letter and its status, null for letters of the abecedary that are not revealed yet
+    pub fn to_json(&self) -> JsonValue {
+        json::object! {
+            letter: self.character.as_str(),
+            status: self.is_revealed().then(|| self.status.code()),
+        }
+    }
+}
+
+/// The chars of a word as a json array, in order
+pub fn json_word(chars: &[Char]) -> JsonValue {
+    chars.iter().map(Char::to_json).collect::<Vec<_>>().into()
+}
+
+/// Joins the colored chars separating them with spaces, the way words are shown to the player
+pub fn colored_word(chars: &[Char]) -> String {
+    chars.iter().fold(String::new(), |s, c| format!("{} {}", s, c.colored))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_decomposed_letter_is_a_single_letter() {
+        let word = letters("a\u{301}rbol");
+        assert_eq!(word.len(), 5);
+        assert_eq!(word[0], "á");
+        assert_eq!(word, letters("árbol"));
+    }
+
+    #[test]
+    fn a_syllable_is_a_single_letter() {
+        assert_eq!(letters("किताब"), ["कि", "ता", "ब"]);
+    }
+
+    #[test]
+    fn letters_are_shown_in_uppercase() {
+        assert_eq!(uppercase("á"), "Á");
+        assert_eq!(uppercase("ñ"), "Ñ");
+    }
+
+    #[test]
+    fn letters_without_a_single_uppercase_letter_stay_as_they_are() {
+        assert_eq!(uppercase("ß"), "ß");
+    }
+
+    #[test]
+    fn a_final_sigma_is_shown_like_any_other() {
+        assert_eq!(uppercase("ς"), "Σ");
+        assert_eq!(uppercase("σ"), "Σ");
+    }
+
+    #[test]
+    fn letters_get_their_color_once_revealed() {
+        let mut c = Char::new(String::from("a"));
+        assert!(!c.is_revealed());
+        c.set_status(CharStatus::Misplaced);
+        assert!(c.is_revealed());
+        assert_eq!(c.status, CharStatus::Misplaced);
+    }
 }
@@ -0,0 +1,51 @@
+use chrono::{Days, Local, NaiveDate};
+
+/// Date of the first daily word, puzzle numbers count the days since then
+const FIRST_DAY: (i32, u32, u32) = (2025, 1, 1);
+
+// FNV-1a, used instead of std's hasher because its output is not guaranteed to stay the same between releases
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// The date of the player's computer, the daily word changes at their midnight
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Reads a date written like "2025-01-31"
+pub fn parse_date(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()
+}
+
+/// Number of the daily puzzle of the date, the first one is 0
+pub fn puzzle_number(date: NaiveDate) -> i64 {
+    let (year, month, day) = FIRST_DAY;
+    let first_day = NaiveDate::from_ymd_opt(year, month, day).expect("first day should be a valid date");
+    (date - first_day).num_days()
+}
+
+/// Date of the daily puzzle with that number
+pub fn date(puzzle_number: i64) -> NaiveDate {
+    let (year, month, day) = FIRST_DAY;
+    let first_day = NaiveDate::from_ymd_opt(year, month, day).expect("first day should be a valid date");
+    let days = Days::new(puzzle_number.unsigned_abs());
+    if puzzle_number < 0 {
+        first_day - days
+    } else {
+        first_day + days
+    }
+}
+
+/// FNV-1a hash of the bytes, the same in every release and on every computer
+pub(crate) fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes
+        .into_iter()
+        .fold(FNV_OFFSET, |hash, byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+/// Index of the daily word in a list of `len` sorted secret words. It only depends on the date and the
+/// language, so everyone playing the same language the same day gets the same word
+pub fn index(date: NaiveDate, language: &str, len: usize) -> usize {
+    let hash = fnv1a(language.bytes().chain(puzzle_number(date).to_le_bytes()));
+    (hash % len as u64) as usize
+}
@@ -1,70 +1,229 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
+use chrono::NaiveDate;
+use rand::seq::SliceRandom;
 use rand::Rng;
 
-use crate::ascii::{asciify_str, has_non_ascii};
+use crate::alphabet::Alphabet;
+use crate::ascii::Folding;
+use crate::char::{letters, normalize, Letter};
+use crate::daily;
+use crate::solver::Table;
 
+/// Words are indexed by their length in letters, which is not the same as their length in bytes nor in chars
+pub fn word_length(word: &str) -> usize {
+    letters(word).len()
+}
+
+/// The words of a language: the ones that can be guessed and the ones that can be the secret word
 pub struct Dictionary {
-    /// The words in this set are all correct, it would not be reasonable to choose a misspelled word as the secret one
-    secret_words: HashSet<String>,
-    /// Some words are misspelled in this set to improve playability, it's for spanish words with symbols
-    pub dictionary: HashSet<String>,
+    /// The words in this set are all correct, it would not be reasonable to choose a misspelled word as the secret one.
+    /// They are sorted and without duplicates, so choosing one with the same random number always gives the same word
+    secret_words: HashMap<usize, Vec<String>>,
+    /// Some words are misspelled in this set to improve playability, they are the words with diacritics written without them
+    dictionary: HashMap<usize, HashSet<String>>,
     /// Al valid characters are in this set
-    pub abecedary: HashSet<char>,
+    pub abecedary: HashSet<Letter>,
+    /// How the letters of the language are compared
+    pub folding: Folding,
+    /// How the letters of the language are sorted and laid out on its keyboard
+    pub alphabet: Alphabet,
+    /// Words and patterns the solver uses for each length, built the first time they are needed
+    pub(crate) tables: Mutex<HashMap<usize, Arc<Table>>>,
+    /// Where the solver saves the patterns, so they are not computed again every time the game starts
+    pub(crate) cache_dir: Option<PathBuf>,
+}
+
+/// Words of a list, one per line. Blank lines are skipped and words are lowercased and normalized, so lists written by hand also work
+fn read_words(bytes: &[u8]) -> Vec<String> {
+    let text: &str = match std::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(e) => panic!("there was an error while reading dictionary: {}", e),
+    };
+    text.lines()
+        .map(|s| normalize(&s.trim().to_lowercase()))
+        .filter(|s| !s.is_empty())
+        .collect()
 }
 
 impl Dictionary {
     /// Reads text, extracts all the words and transforms it in three things:
-    /// A set with some misspelled words for gameplay, a set to choose secret word from, and the abecedary containing all valid chars
-    pub fn new(dictionary_bytes: &[u8], secret_word_bytes: &[u8]) -> Self {
-        let mut set_secret: HashSet<String> = HashSet::new();
-        let mut set_dictionary: HashSet<String> = HashSet::new();
-        let mut abecedary: HashSet<char> = HashSet::new();
-
-        let text_for_dictionary: String = match std::str::from_utf8(dictionary_bytes) {
-            Ok(s) => String::from(s),
-            Err(e) => panic!("there was an error while reading dictionary: {}", e),
+    /// A set with some misspelled words for gameplay, a set to choose secret word from, and the abecedary containing all valid chars.
+    /// Both sets are indexed by word length, and the misspelled words are the ones written without the letters the folding changes
+    pub fn new(dictionary_bytes: &[u8], secret_word_bytes: &[u8], folding: Folding) -> Self {
+        let mut dictionary = Self {
+            secret_words: HashMap::new(),
+            dictionary: HashMap::new(),
+            abecedary: HashSet::new(),
+            folding,
+            alphabet: Alphabet::default(),
+            tables: Mutex::default(),
+            cache_dir: None,
         };
+        dictionary.extend(dictionary_bytes, secret_word_bytes);
+        dictionary
+    }
 
-        for s in text_for_dictionary.lines() {
-            for c in s.to_lowercase().chars() {
-                abecedary.insert(c);
-            }
-            if has_non_ascii(s) {
-                set_dictionary.insert(asciify_str(s));
+    /// Sets the order the abecedary is shown in and the keyboard of the language
+    pub fn with_alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet;
+        self
+    }
+
+    /// Sets the directory where the patterns of the solver are saved
+    pub fn with_cache_dir(mut self, dir: PathBuf) -> Self {
+        self.cache_dir = Some(dir);
+        self
+    }
+
+    /// Adds the words of more lists, like the ones the player can put in their data directory.
+    /// Secret words can also be guessed, so they are added to both sets
+    pub fn extend(&mut self, dictionary_bytes: &[u8], secret_word_bytes: &[u8]) {
+        let secret_words = read_words(secret_word_bytes);
+        // The words change, so the solver must build its tables again
+        self.tables = Mutex::default();
+
+        for s in read_words(dictionary_bytes).iter().chain(secret_words.iter()) {
+            self.abecedary.extend(letters(s));
+            let words = self.dictionary.entry(word_length(s)).or_default();
+            if self.folding.has_foldable(s) {
+                let folded = self.folding.fold_str(s);
+                // The folded letters must also be valid, even if no word has them unfolded
+                self.abecedary.extend(letters(&folded));
+                words.insert(folded);
             }
-            set_dictionary.insert(s.to_owned());
+            words.insert(s.to_owned());
         }
 
-        let text_for_secret: String = match std::str::from_utf8(secret_word_bytes) {
-            Ok(s) => String::from(s),
-            Err(e) => panic!("there was an error while reading dictionary: {}", e),
-        };
+        for s in secret_words {
+            self.secret_words.entry(word_length(&s)).or_default().push(s);
+        }
+        for secret_words in self.secret_words.values_mut() {
+            secret_words.sort();
+            secret_words.dedup();
+        }
+    }
 
-        for s in text_for_secret.lines() {
-            for c in s.to_lowercase().chars() {
-                abecedary.insert(c);
+    /// Chooses a random secret word with the given length, none if there are no secret words that long
+    pub fn get_secret_word<R: Rng + ?Sized>(&self, length: usize, rng: &mut R) -> Option<Vec<Letter>> {
+        self.get_secret_words(length, 1, rng)?.pop()
+    }
+
+    /// Chooses different random secret words with the given length, one for each board of a multi board game.
+    /// None if there are not enough secret words that long
+    pub fn get_secret_words<R: Rng + ?Sized>(
+        &self,
+        length: usize,
+        count: usize,
+        rng: &mut R,
+    ) -> Option<Vec<Vec<Letter>>> {
+        let secret_words = self.secret_words.get(&length)?;
+        if secret_words.len() < count {
+            return None;
+        }
+        let mut chosen: Vec<&String> = Vec::with_capacity(count);
+        while chosen.len() < count {
+            let secret_word = secret_words.choose(rng)?;
+            if !chosen.contains(&secret_word) {
+                chosen.push(secret_word);
             }
-            set_secret.insert(s.to_owned());
         }
+        Some(chosen.iter().map(|secret_word| letters(secret_word)).collect())
+    }
 
-        Self {
-            secret_words: set_secret,
-            dictionary: set_dictionary,
-            abecedary,
+    /// Secret words with the given length that share no letter with the word, comparing the letters folded
+    /// so "á" is taken as "a"
+    pub fn disjoint_secret_words(&self, length: usize, word: &[Letter]) -> Vec<&String> {
+        let folded: HashSet<String> = word.iter().map(|letter| self.folding.fold_str(letter)).collect();
+        self.secret_words(length)
+            .iter()
+            .filter(|secret| {
+                letters(secret)
+                    .iter()
+                    .all(|letter| !folded.contains(&self.folding.fold_str(letter)))
+            })
+            .collect()
+    }
+
+    /// Chooses two random secret words with the given length that share no letter, like the ones of Xordle.
+    /// None if every pair of secret words that long shares some letter
+    pub fn get_disjoint_secret_words<R: Rng + ?Sized>(&self, length: usize, rng: &mut R) -> Option<[Vec<Letter>; 2]> {
+        let mut secret_words: Vec<&String> = self.secret_words(length).iter().collect();
+        secret_words.shuffle(rng);
+        secret_words.into_iter().find_map(|first| {
+            let first = letters(first);
+            let second = letters(self.disjoint_secret_words(length, &first).choose(rng)?);
+            Some([first, second])
+        })
+    }
+
+    /// Chooses the secret word of the day for the language, none if there are no secret words with the given length
+    pub fn get_daily_word(&self, length: usize, date: NaiveDate, language: &str) -> Option<Vec<Letter>> {
+        self.get_daily_words(length, 1, date, language)?.pop()
+    }
+
+    /// Chooses different secret words of the day for each board of a multi board game.
+    /// None if there are not enough secret words with the given length
+    pub fn get_daily_words(
+        &self,
+        length: usize,
+        count: usize,
+        date: NaiveDate,
+        language: &str,
+    ) -> Option<Vec<Vec<Letter>>> {
+        let secret_words = self.secret_words.get(&length)?;
+        if secret_words.len() < count {
+            return None;
+        }
+        let mut indexes: Vec<usize> = Vec::with_capacity(count);
+        for board in 0..count {
+            // The first board gets the same word as a single board game
+            let key = if board == 0 {
+                language.to_owned()
+            } else {
+                format!("{}{}", language, board)
+            };
+            let mut index = daily::index(date, &key, secret_words.len());
+            while indexes.contains(&index) {
+                index = (index + 1) % secret_words.len();
+            }
+            indexes.push(index);
         }
+        Some(indexes.iter().map(|i| letters(&secret_words[*i])).collect())
     }
 
-    pub fn get_secret_word(&self) -> Vec<char> {
-        let index: usize = rand::thread_rng().gen_range(0..self.secret_words.len());
-        let secret_word: Vec<char> = self
-            .secret_words
-            .iter()
-            .nth(index)
-            .unwrap()
-            .to_owned()
-            .chars()
-            .collect();
-        secret_word
+    /// Secret words with the given length, sorted
+    pub fn secret_words(&self, length: usize) -> &[String] {
+        self.secret_words.get(&length).map_or(&[], Vec::as_slice)
+    }
+
+    /// Words accepted as guesses with the given length, including the ones written without their accents
+    pub fn words(&self, length: usize) -> impl Iterator<Item = &String> {
+        self.dictionary.get(&length).into_iter().flatten()
+    }
+
+    /// Checks if the word is accepted as a guess. Words written with only some of their letters folded,
+    /// like "λογος" for "λόγος", are also accepted
+    pub fn contains(&self, word: &str) -> bool {
+        let word = normalize(word);
+        self.dictionary
+            .get(&word_length(&word))
+            .is_some_and(|words| words.contains(&word) || words.contains(&self.folding.fold_str(&word)))
+    }
+
+    /// Adds a word to the ones accepted as guesses, for example a secret word given by the player
+    pub fn insert(&mut self, word: String) {
+        let word = normalize(&word);
+        self.tables = Mutex::default();
+        self.dictionary.entry(word_length(&word)).or_default().insert(word);
+    }
+
+    /// Lengths for which there is at least one secret word, sorted
+    pub fn lengths(&self) -> Vec<usize> {
+        let mut lengths: Vec<usize> = self.secret_words.keys().copied().collect();
+        lengths.sort();
+        lengths
     }
 }
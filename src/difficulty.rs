@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use json::JsonValue;
+
+use crate::ascii::Folding;
+use crate::char::{Char, CharStatus, Letter};
+
+/// How much the hints revealed restrict the next guesses
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Difficulty {
+    /// Any word in the dictionary can be guessed
+    #[default]
+    Normal,
+    /// Correct letters must stay in place and misplaced letters must be used again
+    Hard,
+    /// Like hard, but misplaced letters can't be tried in the same position again and
+    /// incorrect letters can't be used again
+    Strict,
+}
+
+impl FromStr for Difficulty {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            "strict" => Ok(Difficulty::Strict),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A hint revealed by a previous guess that the new guess does not respect.
+/// Positions start at 0
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Constraint {
+    /// The letter was correct in that position, so it must stay there
+    MustBeAt {
+        /// Position of the letter, starting at 0
+        position: usize,
+        /// The letter that was correct
+        letter: Letter,
+    },
+    /// The letter is in the secret word, so the guess must have it
+    MustContain {
+        /// The letter that was misplaced
+        letter: Letter,
+    },
+    /// The letter was misplaced in that position, so it can't go there again. Only in strict
+    MustNotBeAt {
+        /// Position of the letter, starting at 0
+        position: usize,
+        /// The letter that was misplaced
+        letter: Letter,
+    },
+    /// The letter is not in the secret word, so the guess can't have it. Only in strict
+    MustNotContain {
+        /// The letter that was incorrect
+        letter: Letter,
+    },
+}
+
+impl Constraint {
+    /// Name of the constraint in json output, the name of its message without "err_", like "must_be_at"
+    pub fn code(&self) -> &'static str {
+        match self {
+            Constraint::MustBeAt { .. } => "must_be_at",
+            Constraint::MustContain { .. } => "must_contain",
+            Constraint::MustNotBeAt { .. } => "must_not_be_at",
+            Constraint::MustNotContain { .. } => "must_not_contain",
+        }
+    }
+
+    /// The code of the constraint as the reason, its letter and its position if it has one
+    pub fn to_json(&self) -> JsonValue {
+        let (position, letter) = match self {
+            Constraint::MustBeAt { position, letter } | Constraint::MustNotBeAt { position, letter } => {
+                (Some(*position), letter)
+            }
+            Constraint::MustContain { letter } | Constraint::MustNotContain { letter } => (None, letter),
+        };
+        let mut value = json::object! { reason: self.code(), letter: letter.as_str() };
+        if let Some(position) = position {
+            value["position"] = position.into();
+        }
+        value
+    }
+}
+
+/// Letters are compared ignoring accents, the same way guesses are compared to the secret word
+fn same_letter(a: &str, b: &str, folding: &Folding) -> bool {
+    folding.fold_str(a) == folding.fold_str(b)
+}
+
+fn count(word: &[Letter], letter: &str, folding: &Folding) -> usize {
+    word.iter().filter(|c| same_letter(c, letter, folding)).count()
+}
+
+/// Letters found in the row, counting how many times each one was found
+fn found_letters(row: &[Char], folding: &Folding) -> HashMap<Letter, usize> {
+    let mut found: HashMap<Letter, usize> = HashMap::new();
+    for c in row.iter().filter(|c| c.status != CharStatus::Incorrect) {
+        *found.entry(folding.fold_str(&c.character)).or_default() += 1;
+    }
+    found
+}
+
+/// Checks the rules of hard difficulty against a single row of the history
+fn check_hard(row: &[Char], guess: &[Letter], folding: &Folding) -> Result<(), Constraint> {
+    for (position, c) in row.iter().enumerate() {
+        if c.status == CharStatus::Correct && !same_letter(&guess[position], &c.character, folding) {
+            return Err(Constraint::MustBeAt {
+                position,
+                letter: c.character.clone(),
+            });
+        }
+    }
+    // A letter found twice in a row must be used twice too
+    let found = found_letters(row, folding);
+    for c in row.iter().filter(|c| c.status == CharStatus::Misplaced) {
+        if count(guess, &c.character, folding) < found[&folding.fold_str(&c.character)] {
+            return Err(Constraint::MustContain {
+                letter: c.character.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks the rules strict difficulty adds to hard against a single row of the history
+fn check_strict(row: &[Char], guess: &[Letter], folding: &Folding) -> Result<(), Constraint> {
+    for (position, c) in row.iter().enumerate() {
+        if c.status == CharStatus::Misplaced && same_letter(&guess[position], &c.character, folding) {
+            return Err(Constraint::MustNotBeAt {
+                position,
+                letter: c.character.clone(),
+            });
+        }
+    }
+    let found = found_letters(row, folding);
+    for c in row.iter().filter(|c| c.status == CharStatus::Incorrect) {
+        // If the letter was also found in the row, it is in the secret word, just not that many times
+        if !found.contains_key(&folding.fold_str(&c.character)) && count(guess, &c.character, folding) > 0 {
+            return Err(Constraint::MustNotContain {
+                letter: c.character.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks the guess respects the hints revealed by every row of the history.
+/// In normal difficulty every guess is accepted
+pub fn check(
+    difficulty: Difficulty,
+    history: &[Vec<Char>],
+    guess: &[Letter],
+    folding: &Folding,
+) -> Result<(), Constraint> {
+    if difficulty == Difficulty::Normal {
+        return Ok(());
+    }
+    history.iter().try_for_each(|row| check_hard(row, guess, folding))?;
+    if difficulty == Difficulty::Strict {
+        history.iter().try_for_each(|row| check_strict(row, guess, folding))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::char::letters;
+
+    /// A row of the history written with one color for each letter: "g" for correct, "y" for misplaced and "."
+    /// for incorrect
+    fn row(word: &str, colors: &str) -> Vec<Char> {
+        letters(word)
+            .into_iter()
+            .zip(colors.chars())
+            .map(|(letter, color)| {
+                let mut c = Char::new(letter);
+                c.set_status(match color {
+                    'g' => CharStatus::Correct,
+                    'y' => CharStatus::Misplaced,
+                    _ => CharStatus::Incorrect,
+                });
+                c
+            })
+            .collect()
+    }
+
+    fn checked(difficulty: Difficulty, history: &[Vec<Char>], guess: &str) -> Result<(), Constraint> {
+        check(difficulty, history, &letters(guess), &Folding::default())
+    }
+
+    #[test]
+    fn normal_accepts_anything() {
+        let history = [row("crane", "..g.y")];
+        assert_eq!(checked(Difficulty::Normal, &history, "bring"), Ok(()));
+    }
+
+    #[test]
+    fn hard_keeps_correct_letters_in_place() {
+        let history = [row("crane", "..g..")];
+        assert_eq!(checked(Difficulty::Hard, &history, "beach"), Ok(()));
+        assert_eq!(
+            checked(Difficulty::Hard, &history, "adieu"),
+            Err(Constraint::MustBeAt {
+                position: 2,
+                letter: String::from("a")
+            })
+        );
+    }
+
+    #[test]
+    fn hard_uses_every_misplaced_letter_as_many_times_as_it_was_found() {
+        let history = [row("geese", ".yy..")];
+        assert_eq!(
+            checked(Difficulty::Hard, &history, "bread"),
+            Err(Constraint::MustContain {
+                letter: String::from("e")
+            })
+        );
+        assert_eq!(checked(Difficulty::Hard, &history, "eerie"), Ok(()));
+        // An incorrect copy of a letter found in the same row does not ask for a third one
+        let history = [row("speed", "..y..")];
+        assert_eq!(checked(Difficulty::Hard, &history, "bread"), Ok(()));
+    }
+
+    #[test]
+    fn hard_accepts_incorrect_letters_again() {
+        let history = [row("crane", ".....")];
+        assert_eq!(checked(Difficulty::Hard, &history, "bring"), Ok(()));
+    }
+
+    #[test]
+    fn strict_rejects_incorrect_letters_and_misplaced_ones_in_the_same_place() {
+        let history = [row("crane", ".y...")];
+        assert_eq!(
+            checked(Difficulty::Strict, &history, "bring"),
+            Err(Constraint::MustNotBeAt {
+                position: 1,
+                letter: String::from("r")
+            })
+        );
+        assert_eq!(checked(Difficulty::Strict, &history, "forty"), Ok(()));
+        assert_eq!(
+            checked(Difficulty::Strict, &history, "rebus"),
+            Err(Constraint::MustNotContain {
+                letter: String::from("e")
+            })
+        );
+        // The second "e" of SPEED was incorrect, but the first one was found, so "e" is still in the secret word
+        let history = [row("speed", "..y..")];
+        assert_eq!(checked(Difficulty::Strict, &history, "eerie"), Ok(()));
+    }
+
+    #[test]
+    fn accented_letters_are_the_same_letter() {
+        let history = [row("árbol", "g....")];
+        assert_eq!(checked(Difficulty::Hard, &history, "avión"), Ok(()));
+    }
+}
@@ -1,61 +1,136 @@
-use crate::ascii::compare_chars;
+use crate::alphabet::Alphabet;
+use crate::ascii::Folding;
 use crate::char::{CharStatus::*, *};
+use crate::random::GameRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
 use std::collections::{HashMap, HashSet};
 
+/// Compares guesses to a secret word and keeps what they revealed: the colored rows and the abecedary.
+/// Repeated letters are colored like in Wordle, a letter is only misplaced while the secret word has
+/// copies of it that are not colored yet
 pub struct Feedback {
     /// Contains the characters of the secret word
     secret: Vec<Char>,
     /// Contains the characters of the guess
     guess: Vec<Char>,
-    /// Contains all the words guessed
-    feedback_history: Vec<String>,
+    /// Contains all the words guessed, with their chars colored as they are shown
+    feedback_history: Vec<Vec<Char>>,
+    /// Contains all the words guessed, with the colors they really got. The same as the shown ones unless letters lie
+    true_history: Vec<Vec<Char>>,
+    /// Position of the letter that lied in each row of the history, none in rows without a lie
+    lies: Vec<Option<usize>>,
+    /// Chooses the letter that lies in each row and what it says, none if no letter lies
+    liar: Option<GameRng>,
     /// Contains the abecedary with its chars colored to show its status
-    abecedary: HashMap<char, Char>,
+    abecedary: HashMap<Letter, Char>,
+    /// How the letters of the guess are compared to the ones of the secret word
+    folding: Folding,
+    /// How the abecedary is sorted
+    alphabet: Alphabet,
     /// True if the guess is correct
     pub win: bool,
 }
 
 impl Feedback {
-    pub fn new(secret_word: &[char], abecedary: &HashSet<char>) -> Self {
+    /// Nothing is revealed yet, every letter of the abecedary is shown without color
+    pub fn new(secret_word: &[Letter], abecedary: &HashSet<Letter>, folding: &Folding, alphabet: &Alphabet) -> Self {
         Self {
-            secret: secret_word.iter().copied().map(Char::new).collect(),
+            secret: secret_word.iter().cloned().map(Char::new).collect(),
             guess: Vec::new(),
             feedback_history: Vec::new(),
-            abecedary: abecedary.iter().map(|c| (*c, Char::new(*c))).collect(),
+            true_history: Vec::new(),
+            lies: Vec::new(),
+            liar: None,
+            abecedary: abecedary.iter().map(|c| (c.clone(), Char::new(c.clone()))).collect(),
+            folding: folding.clone(),
+            alphabet: alphabet.clone(),
             win: false,
         }
     }
 
+    /// Makes one letter of each row show a wrong status, like in Fibble. The rng chooses which letter and
+    /// which of the other two statuses it shows. The winning row never lies
+    pub fn with_lies(mut self, rng: GameRng) -> Self {
+        self.liar = Some(rng);
+        self
+    }
+
     fn reset(&mut self, new: String) {
         self.secret.iter_mut().for_each(|c| c.status = Incorrect);
-        self.guess = new.chars().map(Char::new).collect();
+        self.guess = letters(&new).into_iter().map(Char::new).collect();
         self.win = true;
     }
 
+    /// Colors the guess, which must be as long as the secret word, and adds it to the history.
+    /// Sets [`Feedback::win`] if every letter is correct
     pub fn compare(&mut self, guess: String) {
         self.reset(guess);
+        // The abecedary must only show what the row shows, or it would give the lie away
+        let abecedary = self.liar.is_some().then(|| self.abecedary.clone());
         self.mark_correct();
         self.mark_misplaced();
         self.mark_incorrect();
-        self.feedback_history.push(self.get_feedback());
+        self.true_history.push(self.guess.clone());
+
+        let lie = self.lie();
+        if let (Some(_), Some(abecedary)) = (lie, abecedary) {
+            self.abecedary = abecedary;
+            self.color_shown();
+        }
+        self.lies.push(lie);
+        self.feedback_history.push(self.guess.clone());
     }
 
-    fn color_abecedary(&mut self, letter: char, color: CharStatus) {
-        self.abecedary.get_mut(&letter).unwrap().set_status(color);
+    /// Changes the status of a random letter of the guess to one of the other two, none if letters don't lie
+    fn lie(&mut self) -> Option<usize> {
+        if self.win {
+            return None;
+        }
+        let rng = self.liar.as_mut()?;
+        let position = rng.gen_range(0..self.guess.len());
+        let statuses: Vec<CharStatus> = [Correct, Misplaced, Incorrect]
+            .into_iter()
+            .filter(|status| *status != self.guess[position].status)
+            .collect();
+        let status = statuses
+            .choose(rng)
+            .expect("there are always two other statuses")
+            .clone();
+        self.guess[position].set_status(status);
+        Some(position)
+    }
+
+    /// Colors the abecedary with the statuses the guess shows, in the same order the marks color it
+    fn color_shown(&mut self) {
+        for status in [Correct, Misplaced, Incorrect] {
+            for i in 0..self.guess.len() {
+                if self.guess[i].status == status {
+                    self.color_abecedary(&self.guess[i].character.clone(), status.clone());
+                }
+            }
+        }
+    }
+
+    fn color_abecedary(&mut self, letter: &str, color: CharStatus) {
+        self.abecedary.get_mut(letter).unwrap().set_status(color);
     }
 
     fn mark_correct(&mut self) {
         self.win = true;
         for i in 0..self.guess.len() {
-            if compare_chars(self.guess[i].character, self.secret[i].character) {
+            if self
+                .folding
+                .compare_letters(&self.guess[i].character, &self.secret[i].character)
+            {
                 self.guess[i].set_status(Correct);
                 self.secret[i].set_status(Correct);
                 if self.guess[i].character != self.secret[i].character {
                     // If this is true, it means the actual letter was the one in the secret word
                     // In guess: "a" - In secret: "á", so "á" must be set as correct, not "a"
-                    self.color_abecedary(self.secret[i].character, Correct);
+                    self.color_abecedary(&self.secret[i].character.clone(), Correct);
                 } else {
-                    self.color_abecedary(self.guess[i].character, Correct);
+                    self.color_abecedary(&self.guess[i].character.clone(), Correct);
                 }
                 continue;
             }
@@ -73,13 +148,16 @@ impl Feedback {
                 if !self.secret[j].is_incorrect() {
                     continue;
                 }
-                if compare_chars(self.guess[i].character, self.secret[j].character) {
+                if self
+                    .folding
+                    .compare_letters(&self.guess[i].character, &self.secret[j].character)
+                {
                     self.guess[i].set_status(Misplaced);
                     self.secret[j].set_status(Misplaced);
                     if self.guess[i].character != self.secret[j].character {
-                        self.color_abecedary(self.secret[j].character, Misplaced);
+                        self.color_abecedary(&self.secret[j].character.clone(), Misplaced);
                     } else {
-                        self.color_abecedary(self.guess[i].character, Misplaced);
+                        self.color_abecedary(&self.guess[i].character.clone(), Misplaced);
                     }
                     break; // Must break to avoid marking all instances of self.guess[i] in secret word as misplaced
                 }
@@ -91,35 +169,105 @@ impl Feedback {
         for i in 0..self.guess.len() {
             if self.guess[i].is_incorrect() {
                 self.guess[i].set_status(Incorrect);
-                self.color_abecedary(self.guess[i].character, Incorrect);
+                self.color_abecedary(&self.guess[i].character.clone(), Incorrect);
             }
         }
     }
 
     // Getters
 
+    /// The secret word as it is written
     pub fn get_secret(&self) -> String {
         self.secret
             .iter()
             .fold(String::new(), |s, c| format!("{}{}", s, c.character))
     }
 
-    pub fn get_feedback(&self) -> String {
-        self.guess
-            .iter()
-            .fold(String::new(), |s, c| format!("{} {}", s, c.colored))
+    /// The last word guessed with its chars colored, empty if nothing has been guessed
+    pub fn get_guess(&self) -> &[Char] {
+        &self.guess
     }
 
-    pub fn get_abecedary(&self) -> String {
-        let mut abecedary_vec: Vec<Char> =
-            self.abecedary.clone().into_iter().map(|x| x.1).collect();
-        abecedary_vec.sort_by(|a, b| a.character.cmp(&b.character));
+    /// The abecedary in alphabetical order, with its chars colored to show the hints revealed so far
+    pub fn get_abecedary(&self) -> Vec<Char> {
+        let mut abecedary_vec: Vec<Char> = self.abecedary.clone().into_iter().map(|x| x.1).collect();
+        self.alphabet.sort(&mut abecedary_vec);
         abecedary_vec
-            .into_iter()
-            .fold(String::new(), |s, c| format!("{} {}", s, c.colored))
     }
 
-    pub fn get_history(&self) -> &Vec<String> {
+    /// All the words guessed with their chars colored as they were shown
+    pub fn get_history(&self) -> &Vec<Vec<Char>> {
         &self.feedback_history
     }
+
+    /// All the words guessed with the colors they really got, to reveal the lies once the game is over
+    pub fn get_true_history(&self) -> &[Vec<Char>] {
+        &self.true_history
+    }
+
+    /// Position of the letter that lied in each row, none in the rows without a lie
+    pub fn get_lies(&self) -> &[Option<usize>] {
+        &self.lies
+    }
+
+    /// Checks if one letter of each row lies
+    pub fn has_lies(&self) -> bool {
+        self.liar.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random;
+
+    const GUESSES: [&str; 5] = ["crane", "slate", "moist", "point", "build"];
+
+    /// Feedback for "fight" with the guesses compared, with the lies chosen by the seed
+    fn played(seed: u64, guesses: &[&str]) -> Feedback {
+        let abecedary: HashSet<Letter> = letters("abcdefghijklmnopqrstuvwxyz").into_iter().collect();
+        let mut feedback = Feedback::new(&letters("fight"), &abecedary, &Folding::default(), &Alphabet::default())
+            .with_lies(random::from_seed(seed));
+        for guess in guesses {
+            feedback.compare(guess.to_string());
+        }
+        feedback
+    }
+
+    fn statuses(row: &[Char]) -> Vec<CharStatus> {
+        row.iter().map(|c| c.status.clone()).collect()
+    }
+
+    #[test]
+    fn exactly_one_letter_of_each_row_lies() {
+        for seed in 0..20 {
+            let feedback = played(seed, &GUESSES);
+            let rows = feedback.get_history().iter().zip(feedback.get_true_history());
+            for (lie, (shown, truth)) in feedback.get_lies().iter().zip(rows) {
+                let position = lie.expect("every row but the winning one lies");
+                let different: Vec<usize> = (0..shown.len())
+                    .filter(|&i| shown[i].status != truth[i].status)
+                    .collect();
+                assert_eq!(different, [position]);
+            }
+        }
+    }
+
+    #[test]
+    fn the_winning_row_never_lies() {
+        for seed in 0..20 {
+            let feedback = played(seed, &["crane", "fight"]);
+            assert!(feedback.win);
+            assert_eq!(feedback.get_lies()[1], None);
+            assert_eq!(statuses(&feedback.get_history()[1]), vec![Correct; 5]);
+        }
+    }
+
+    #[test]
+    fn the_same_seed_tells_the_same_lies() {
+        let first = played(7, &GUESSES);
+        let second = played(7, &GUESSES);
+        assert_eq!(first.get_lies(), second.get_lies());
+        assert_eq!(first.get_history(), second.get_history());
+    }
 }
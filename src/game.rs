@@ -1,145 +1,481 @@
-use crate::feedback::Feedback;
-use crate::language::LanguagePack;
 use crate::prompt::*;
+use another_wordle_clone::absurdle::AbsurdleSession;
+use another_wordle_clone::char::{colored_word, letters, Char, CharStatus, Letter};
+use another_wordle_clone::language::with_length;
+use another_wordle_clone::multi::{MultiEvent, MultiSession};
+use another_wordle_clone::share::{share, share_multi, share_xordle, Puzzle};
+use another_wordle_clone::solver::{parse_pattern, Solver, Suggestions, SUGGESTIONS};
+use another_wordle_clone::xordle::XordleSession;
+use another_wordle_clone::{
+    Command, Dictionary, Difficulty, Event, Feedback, GameSession, InvalidGuess, LanguagePack, Session, Stats,
+};
+use std::str::FromStr;
 
 use colored::Colorize;
-use std::collections::HashSet;
+
+/// How games are played, set with the arguments
+pub struct Options {
+    pub max_tries: u8,
+    pub difficulty: Difficulty,
+    /// Write the result grid with plain ASCII instead of emojis
+    pub ascii: bool,
+    /// Show the abecedary laid out as the keyboard of the language
+    pub keyboard: bool,
+    /// Seed that chooses the letter that lies in each row, none if no letter lies
+    pub lies: Option<u64>,
+}
 
 fn error(message: &str) {
     println!("{}\n", message.red().bold())
 }
 
-fn validate(
-    guess: &str,
-    words: &HashSet<String>,
-    abecedary: &HashSet<char>,
-    lang: &LanguagePack,
-) -> bool {
-    for c in guess.chars() {
-        if !abecedary.contains(&c) {
-            error(lang.err_invalid_chars);
-            return false;
-        }
+fn print_word(word: &[Char]) {
+    println!("{}\n", colored_word(word).bold());
+}
+
+fn print_tries_left(lang: &LanguagePack, tries_left: u8) {
+    println!(
+        "{}: {}\n",
+        lang.tries_left.bold(),
+        tries_left.to_string().as_str().blue().bold()
+    )
+}
+
+pub fn print_stats(stats: &Stats, lang: &LanguagePack) {
+    println!(
+        "{}: {}  {}: {}  {}: {}  {}: {}\n",
+        lang.stats_played.bold(),
+        stats.played,
+        lang.stats_win_rate.bold(),
+        stats.win_rate(),
+        lang.stats_current_streak.bold(),
+        stats.current_streak,
+        lang.stats_max_streak.bold(),
+        stats.max_streak
+    );
+    println!("{}", lang.stats_guess_distribution.bold());
+    let most = stats.guesses.iter().copied().max().unwrap_or_default().max(1);
+    for (i, wins) in stats.guesses.iter().enumerate() {
+        // Bars are at most 20 characters long, but never empty if there is a win
+        let bar = "█".repeat(((wins * 20).div_ceil(most)) as usize);
+        println!("{:>2} | {} {}", i + 1, bar.as_str().green(), wins);
+    }
+    println!();
+}
+
+/// With this many candidates or less, they are listed
+const LISTED_CANDIDATES: usize = 10;
+
+/// The number of secret words still possible, listing them if they are few, and the best guesses.
+/// Guesses that could be the secret word are green
+fn print_suggestions(suggestions: &Suggestions, lang: &LanguagePack) {
+    let count = suggestions.candidates.len();
+    if count == 0 {
+        error(&lang.solver_no_candidates);
+        return;
     }
-    // Done like that because words with special characters are not recognized to have 5 letters with guess.len() such as árbol, for example
-    if guess.chars().collect::<Vec<char>>().len() != 5 {
-        error(lang.err_invalid_len);
-        return false;
+    print!(
+        "{}",
+        lang.solver_candidates.replace("{count}", &count.to_string()).bold()
+    );
+    if count <= LISTED_CANDIDATES {
+        print!(" ({})", suggestions.candidates.join(", ").to_uppercase());
     }
-    if !words.contains(guess) {
-        error(lang.err_invalid_word);
-        return false;
+    println!();
+    for (i, suggestion) in suggestions.guesses.iter().enumerate() {
+        let word = suggestion.word.to_uppercase();
+        let word = if suggestion.candidate {
+            word.green()
+        } else {
+            word.normal()
+        };
+        let bits = lang
+            .solver_entropy
+            .replace("{bits}", &format!("{:.2}", suggestion.entropy));
+        println!("{:>2}. {} {}", i + 1, word.bold(), bits);
     }
-    true
+    println!();
 }
 
-fn print_feedback(feedback: &Feedback, lang: &LanguagePack) {
-    let word = feedback.get_feedback().as_str().bold();
-    if word.trim().is_empty() {
-        println!("{}\n", lang.no_word_guessed.bold());
+/// The abecedary in a single line, or laid out as the keyboard with each row a bit more to the right
+fn print_abecedary(abecedary: &[Char], dictionary: &Dictionary, keyboard: bool) {
+    if !keyboard {
+        println!("{}", colored_word(abecedary).bold());
         return;
     }
-    println!("{}\n", word);
+    let rows = dictionary.alphabet.keyboard(abecedary, &dictionary.folding);
+    for (i, row) in rows.iter().enumerate() {
+        println!("{}{}", " ".repeat(i), colored_word(row).bold());
+    }
 }
 
-fn print_tries_left(lang: &LanguagePack, tries: u8, max_tries: u8) {
-    println!(
-        "{}: {}\n",
-        lang.tries_left.bold(),
-        (max_tries - tries).to_string().as_str().blue().bold()
-    )
+fn render(event: &Event, lang: &LanguagePack, stats: &Stats, dictionary: &Dictionary, options: &Options) {
+    match event {
+        Event::InvalidGuess(reason) => error(&lang.invalid_guess(reason)),
+        Event::Scored(word) => print_word(word),
+        Event::Win { .. } => println!("{}\n", lang.win.bold()),
+        Event::Loss { secret } => println!("{}: \"{}\"\n", lang.loss.bold(), secret.to_uppercase().bold()),
+        Event::TriesLeft(tries_left) => print_tries_left(lang, *tries_left),
+        Event::Alive(candidates) => println!(
+            "{}: {}\n",
+            lang.absurdle_alive.bold(),
+            candidates.to_string().as_str().blue().bold()
+        ),
+        Event::PreviousWord(Some(word)) => print_word(word),
+        Event::PreviousWord(None) => println!("{}\n", lang.no_word_guessed.bold()),
+        Event::History(history) => {
+            if history.is_empty() {
+                println!("{}\n", lang.no_word_guessed.bold());
+            } else {
+                history
+                    .iter()
+                    .for_each(|word| println!("{}", colored_word(word).bold()));
+                println!();
+            }
+        }
+        Event::Abecedary(abecedary) => {
+            println!();
+            print_abecedary(abecedary, dictionary, options.keyboard);
+            println!();
+        }
+        Event::Suggestions(suggestions) => print_suggestions(suggestions, lang),
+        Event::Statistics => print_stats(stats, lang),
+        Event::Help => println!("{}\n", lang.commands),
+        Event::Quit => println!(),
+    }
 }
 
+/// Name of the color of the status
+fn color_name<'a>(status: &CharStatus, lang: &'a LanguagePack) -> &'a str {
+    match status {
+        CharStatus::Correct => &lang.color_correct,
+        CharStatus::Misplaced => &lang.color_misplaced,
+        CharStatus::Incorrect => &lang.color_incorrect,
+    }
+}
+
+/// Each row that lied as it was shown and as it really was, and which letter lied. Without colors both
+/// rows look the same, so the colors the letter showed and really had are written
+fn print_lies(feedback: &Feedback, lang: &LanguagePack) {
+    let rows = feedback.get_history().iter().zip(feedback.get_true_history());
+    for (lie, (shown, truth)) in feedback.get_lies().iter().zip(rows) {
+        if let Some(position) = *lie {
+            let position_name = lang.ordinal(position + 1);
+            let note = if colored::control::SHOULD_COLORIZE.should_colorize() {
+                lang.fibble_lie.replace("{position}", &position_name)
+            } else {
+                lang.fibble_lie_shown
+                    .replace("{position}", &position_name)
+                    .replace("{shown}", color_name(&shown[position].status, lang))
+                    .replace("{was}", color_name(&truth[position].status, lang))
+            };
+            println!(
+                "{}  →{}  ({})",
+                colored_word(shown).bold(),
+                colored_word(truth).bold(),
+                note
+            );
+        }
+    }
+    println!();
+}
+
+/// How a game ended, for the statistics
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Won with that many tries
+    Won(u8),
+    /// Lost, or quit after guessing at least one word. Giving up counts as losing, or quitting a game
+    /// about to be lost would keep the streak alive
+    Lost,
+    /// Quit before guessing anything, which is not a game played
+    Unplayed,
+}
+
+impl Outcome {
+    fn of(is_won: bool, tries: u8) -> Self {
+        if is_won {
+            Outcome::Won(tries)
+        } else if tries > 0 {
+            Outcome::Lost
+        } else {
+            Outcome::Unplayed
+        }
+    }
+}
+
+/// Plays a game until it's won, lost or quit. The statistics are only shown, the outcome is recorded by the caller
 pub fn start(
-    words: &HashSet<String>,
-    secret_word: &[char],
-    abecedary: &HashSet<char>,
-    max_tries: u8,
+    dictionary: &Dictionary,
+    secret_word: &[Letter],
+    puzzle: Puzzle,
+    options: &Options,
+    language: &str,
     lang: &LanguagePack,
-) {
-    let mut guess: String;
-    let mut feedback = Feedback::new(secret_word, abecedary);
+    stats: &Stats,
+) -> Outcome {
+    let mut session = GameSession::new(dictionary, secret_word, options.max_tries).with_difficulty(options.difficulty);
+    if let Some(seed) = options.lies {
+        session = session.with_lies(seed);
+    }
 
-    let mut tries: u8 = 0;
-    'outer: loop {
-        guess = read_input(lang).trim().to_owned();
-        if guess.is_empty() {
-            continue;
+    while !session.is_over() {
+        // A closed input quits, there is nothing left to guess with
+        let events = match read_input(lang) {
+            Some(input) => session.input(&input),
+            None => vec![session.command(Command::Quit)],
+        };
+        for event in events {
+            render(&event, lang, stats, dictionary, options);
+            if let Event::Win { .. } | Event::Loss { .. } = event {
+                if session.feedback().has_lies() {
+                    println!("{}", lang.fibble_reveal.bold());
+                    print_lies(session.feedback(), lang);
+                }
+                println!("{}\n", share(&session, language, puzzle, lang, options.ascii));
+            }
         }
+    }
+    Outcome::of(session.is_won(), session.tries())
+}
 
-        match guess.to_lowercase().as_str() {
-            "q" => {
-                println!();
+/// Words of every board side by side, boards without a word are left blank
+fn print_boards_row(row: &[Option<Vec<Char>>], length: usize) {
+    let words: Vec<String> = row
+        .iter()
+        .map(|word| match word {
+            Some(word) => colored_word(word),
+            None => " ".repeat(length * 2),
+        })
+        .collect();
+    println!("{}", words.join("   ").bold());
+}
+
+fn render_multi(
+    event: &MultiEvent,
+    lang: &LanguagePack,
+    stats: &Stats,
+    dictionary: &Dictionary,
+    options: &Options,
+    length: usize,
+) {
+    match event {
+        MultiEvent::InvalidGuess(reason) => error(&lang.invalid_guess(reason)),
+        MultiEvent::Scored(row) => {
+            print_boards_row(row, length);
+            println!();
+        }
+        MultiEvent::Solved { board, secret } => println!(
+            "{} {}: {}\n",
+            "✓".green().bold(),
+            board + 1,
+            secret.to_uppercase().green().bold()
+        ),
+        MultiEvent::Win => println!("{}\n", lang.win.bold()),
+        MultiEvent::Loss { secrets } => {
+            let secrets: Vec<String> = secrets
+                .iter()
+                .map(|(board, secret)| format!("{}: \"{}\"", board + 1, secret.to_uppercase()))
+                .collect();
+            println!("{}: {}\n", lang.loss.bold(), secrets.join(", ").bold());
+        }
+        MultiEvent::TriesLeft(tries_left) => print_tries_left(lang, *tries_left),
+        MultiEvent::PreviousWord(Some(row)) => {
+            print_boards_row(row, length);
+            println!();
+        }
+        MultiEvent::PreviousWord(None) => println!("{}\n", lang.no_word_guessed.bold()),
+        MultiEvent::History(boards) => {
+            let tries = boards.iter().map(Vec::len).max().unwrap_or_default();
+            if tries == 0 {
+                println!("{}\n", lang.no_word_guessed.bold());
                 return;
             }
-            "a" => {
-                println!("\n{}\n", feedback.get_abecedary().bold());
-                continue 'outer;
-            }
-            "w" => {
-                // Shows previous word
-                print_feedback(&feedback, lang);
-                continue 'outer;
+            for try_index in 0..tries {
+                let row: Vec<Option<Vec<Char>>> =
+                    boards.iter().map(|history| history.get(try_index).cloned()).collect();
+                print_boards_row(&row, length);
             }
-            "t" => {
-                print_tries_left(lang, tries, max_tries);
-                continue 'outer;
+            println!();
+        }
+        MultiEvent::Abecedary(abecedaries) => {
+            println!();
+            for (board, abecedary) in abecedaries.iter().enumerate() {
+                if options.keyboard {
+                    println!("{}:", board + 1);
+                } else {
+                    print!("{}:", board + 1);
+                }
+                print_abecedary(abecedary, dictionary, options.keyboard);
             }
-            "h" => {
-                println!("{}\n", lang.commands);
-                continue 'outer;
+            println!();
+        }
+        MultiEvent::Suggestions(boards) => {
+            for (board, suggestions) in boards.iter().enumerate() {
+                if let Some(suggestions) = suggestions {
+                    println!("{}:", board + 1);
+                    print_suggestions(suggestions, lang);
+                }
             }
-            "l" => {
-                // Show history
-                let history = feedback.get_history();
-                if history.is_empty() {
-                    println!("{}\n", lang.no_word_guessed.bold());
-                } else {
-                    history.iter().for_each(|s| println!("{}", s.bold()));
-                    println!();
+        }
+        MultiEvent::Statistics => print_stats(stats, lang),
+        MultiEvent::Help => println!("{}\n", lang.commands),
+        MultiEvent::Quit => println!(),
+    }
+}
+
+/// Like [`start`], but every guess is compared against all the secret words, each one on its own board
+pub fn start_multi(
+    dictionary: &Dictionary,
+    secret_words: &[Vec<Letter>],
+    puzzle: Puzzle,
+    options: &Options,
+    language: &str,
+    lang: &LanguagePack,
+    stats: &Stats,
+) -> Outcome {
+    let mut session =
+        MultiSession::new(dictionary, secret_words, options.max_tries).with_difficulty(options.difficulty);
+    if let Some(seed) = options.lies {
+        session = session.with_lies(seed);
+    }
+
+    while !session.is_over() {
+        let events = match read_input(lang) {
+            Some(input) => session.input(&input),
+            None => vec![session.command(Command::Quit)],
+        };
+        for event in events {
+            render_multi(&event, lang, stats, dictionary, options, session.length());
+            if let MultiEvent::Win | MultiEvent::Loss { .. } = event {
+                if options.lies.is_some() {
+                    println!("{}", lang.fibble_reveal.bold());
+                    for (board, board_state) in session.boards().iter().enumerate() {
+                        println!("{}:", board + 1);
+                        print_lies(board_state.feedback(), lang);
+                    }
                 }
-                continue 'outer;
+                println!("{}\n", share_multi(&session, language, puzzle, lang, options.ascii));
             }
-            _ => (),
+        }
+    }
+    Outcome::of(session.is_won(), session.tries())
+}
+
+/// Like [`start_multi`], but both secret words share a single board, see [`XordleSession`].
+/// Statistics are shown but not recorded, they are kept by number of boards
+pub fn start_xordle(
+    dictionary: &Dictionary,
+    secret_words: &[Vec<Letter>; 2],
+    puzzle: Puzzle,
+    options: &Options,
+    language: &str,
+    lang: &LanguagePack,
+    stats: &Stats,
+) {
+    let mut session = XordleSession::new(dictionary, secret_words, options.max_tries);
+
+    while !session.is_over() {
+        let events = match read_input(lang) {
+            Some(input) => session.input(&input),
+            None => vec![session.command(Command::Quit)],
         };
+        for event in events {
+            render_multi(&event, lang, stats, dictionary, options, session.length());
+            if let MultiEvent::Win | MultiEvent::Loss { .. } = event {
+                println!("{}\n", share_xordle(&session, language, puzzle, lang, options.ascii));
+            }
+        }
+    }
+}
 
-        if !validate(&guess, words, abecedary, lang) {
-            continue;
+/// How many secret words were still possible after each guess, like "2315 → 168 → 12 → 1"
+fn print_rounds(alive: &[usize], lang: &LanguagePack) {
+    if alive.is_empty() {
+        return;
+    }
+    let rounds: Vec<String> = alive.iter().map(usize::to_string).collect();
+    println!("{}: {}\n", lang.absurdle_rounds.bold(), rounds.join(" → "));
+}
+
+/// Like [`start`], but the game dodges the guesses instead of having a secret word, see [`AbsurdleSession`].
+/// Statistics are shown but not recorded, a game without a limit of tries can't be compared with the others
+pub fn start_absurdle(mut session: AbsurdleSession, options: &Options, lang: &LanguagePack, stats: &Stats) {
+    let dictionary = session.dictionary();
+    while !session.is_over() {
+        let events = match read_input(lang) {
+            Some(input) => session.input(&input),
+            None => vec![session.command(Command::Quit)],
+        };
+        for event in events {
+            render(&event, lang, stats, dictionary, options);
         }
+    }
+    print_rounds(session.alive(), lang);
+}
 
-        feedback.compare(guess);
+/// Reads colors until they are valid for a guess with that length, none if the input is closed
+fn read_colors(length: usize, lang: &LanguagePack) -> Option<Vec<CharStatus>> {
+    loop {
+        match parse_pattern(&read_pattern(lang)?) {
+            Some(pattern) if pattern.len() == length => return Some(pattern),
+            _ => error(&lang.err_invalid_pattern),
+        }
+    }
+}
 
-        if feedback.win {
-            println!(
-                "\n{}\n",
-                feedback
-                    .get_secret()
-                    .to_uppercase()
-                    .chars()
-                    .fold(String::new(), |mut s, c| {
-                        s.push(' ');
-                        s.push(c);
-                        s
-                    })
-                    .as_str()
-                    .green()
-                    .bold()
-            );
-            println!("{}\n", lang.win.bold());
-            return;
-        } else if tries == max_tries - 1 {
-            print_feedback(&feedback, lang);
-            println!(
-                "{}: \"{}\"\n",
-                lang.loss.bold(),
-                feedback.get_secret().to_uppercase().bold()
-            );
+/// Helps with a game played somewhere else, like the real Wordle: the player writes each guess and the
+/// colors it got, and the best next guesses are suggested. It ends when there is one candidate left or none.
+/// With lies, one color of each row but the winning one is taken as a lie
+pub fn solve(dictionary: &Dictionary, length: usize, lies: bool, lang: &LanguagePack) {
+    let Some(mut solver) = Solver::new(dictionary, length) else {
+        error(&with_length(&lang.err_no_secret_words, length));
+        return;
+    };
+    loop {
+        let suggestions = solver.suggestions(SUGGESTIONS);
+        print_suggestions(&suggestions, lang);
+        if suggestions.candidates.len() <= 1 {
             return;
         }
 
-        print_feedback(&feedback, lang);
+        let Some(input) = read_input(lang) else {
+            return;
+        };
+        if let Ok(Command::Quit) = Command::from_str(&input) {
+            return;
+        }
+        let guess = letters(input.trim());
+        if guess.iter().any(|letter| !dictionary.abecedary.contains(letter)) {
+            error(&lang.invalid_guess(&InvalidGuess::InvalidChars));
+            continue;
+        }
+        if guess.len() != length {
+            error(&lang.invalid_guess(&InvalidGuess::InvalidLength { expected: length }));
+            continue;
+        }
 
-        tries += 1;
-        print_tries_left(lang, tries, max_tries);
+        let Some(pattern) = read_colors(length, lang) else {
+            return;
+        };
+        let row: Vec<Char> = guess
+            .iter()
+            .zip(pattern.iter())
+            .map(|(letter, status)| {
+                let mut c = Char::new(letter.clone());
+                c.set_status(status.clone());
+                c
+            })
+            .collect();
+        print_word(&row);
+        if pattern.iter().all(|status| *status == CharStatus::Correct) {
+            println!("{}\n", lang.win.bold());
+            return;
+        }
+        if lies {
+            solver.update_with_lie(&guess, &pattern);
+        } else {
+            solver.update(&guess, &pattern);
+        }
     }
 }
@@ -0,0 +1,202 @@
+use std::io::BufRead;
+
+use crate::game::Options;
+use another_wordle_clone::absurdle::AbsurdleSession;
+use another_wordle_clone::char::Letter;
+use another_wordle_clone::multi::{MultiEvent, MultiSession};
+use another_wordle_clone::session::lies_json;
+use another_wordle_clone::share::Puzzle;
+use another_wordle_clone::xordle::XordleSession;
+use another_wordle_clone::{Dictionary, Event, GameSession, LanguagePack, Session, Stats};
+use json::JsonValue;
+
+/// Writes the value in a single line, so each event can be read as soon as it happens
+fn emit(value: JsonValue) {
+    println!("{}", value.dump());
+}
+
+/// Lines written to stdin, lowercase like the prompt reads them. It ends when stdin is closed
+fn read_lines() -> impl Iterator<Item = String> {
+    std::io::stdin()
+        .lock()
+        .lines()
+        .map_while(Result::ok)
+        .map(|line| line.to_lowercase())
+}
+
+/// First event of every game, with what a bot needs to know before guessing
+fn start_json(language: &str, length: usize, boards: usize, puzzle: Puzzle, options: &Options) -> JsonValue {
+    let mut value = json::object! {
+        event: "start",
+        language: language,
+        length: length,
+        boards: boards,
+        max_tries: options.max_tries,
+    };
+    match puzzle {
+        Puzzle::Daily(number) => value["daily"] = number.into(),
+        Puzzle::Seeded(seed) => value["seed"] = seed.into(),
+        Puzzle::Chosen => (),
+    }
+    if options.lies.is_some() {
+        value["fibble"] = true.into();
+    }
+    value
+}
+
+/// Adds to the event what the session leaves to the front end: the message of an invalid guess,
+/// the statistics and the available commands
+fn complete(mut value: JsonValue, invalid_guess: Option<String>, lang: &LanguagePack, stats: &Stats) -> JsonValue {
+    if let Some(message) = invalid_guess {
+        value["message"] = message.into();
+    }
+    match value["event"].as_str() {
+        Some("statistics") => value["stats"] = stats.to_json(),
+        Some("help") => value["commands"] = lang.commands.as_str().into(),
+        _ => (),
+    }
+    value
+}
+
+/// Like [`crate::game::start`], but without prompts nor colors: guesses and commands are read from stdin,
+/// one per line, and every event is written to stdout as a json object. The game ends when it is over
+/// or when stdin is closed
+pub fn start(
+    dictionary: &Dictionary,
+    secret_word: &[Letter],
+    puzzle: Puzzle,
+    options: &Options,
+    language: &str,
+    lang: &LanguagePack,
+    stats: &Stats,
+) {
+    let mut session = GameSession::new(dictionary, secret_word, options.max_tries).with_difficulty(options.difficulty);
+    if let Some(seed) = options.lies {
+        session = session.with_lies(seed);
+    }
+
+    emit(start_json(language, session.length(), 1, puzzle, options));
+    for line in read_lines() {
+        for event in session.input(&line) {
+            let message = match &event {
+                Event::InvalidGuess(reason) => Some(lang.invalid_guess(reason)),
+                _ => None,
+            };
+            let mut value = complete(event.to_json(), message, lang, stats);
+            if matches!(event, Event::Win { .. } | Event::Loss { .. }) && session.feedback().has_lies() {
+                value["lies"] = lies_json(session.feedback());
+            }
+            emit(value);
+        }
+        if session.is_over() {
+            return;
+        }
+    }
+}
+
+/// Like [`start`], but the game dodges the guesses, see [`AbsurdleSession`]. The start has no tries nor
+/// seed, and the win and the quit say how many secret words were still possible after each guess
+pub fn start_absurdle(
+    mut session: AbsurdleSession,
+    options: &Options,
+    language: &str,
+    lang: &LanguagePack,
+    stats: &Stats,
+) {
+    let mut start = start_json(language, session.length(), 1, Puzzle::Chosen, options);
+    start.remove("max_tries");
+    start["absurdle"] = true.into();
+    emit(start);
+    for line in read_lines() {
+        for event in session.input(&line) {
+            let message = match &event {
+                Event::InvalidGuess(reason) => Some(lang.invalid_guess(reason)),
+                _ => None,
+            };
+            let mut value = complete(event.to_json(), message, lang, stats);
+            if let Event::Win { .. } | Event::Quit = event {
+                value["alive"] = session.alive().into();
+            }
+            emit(value);
+        }
+        if session.is_over() {
+            return;
+        }
+    }
+}
+
+/// Like [`start_multi`], but both secret words share a single board, see [`XordleSession`]. The start says
+/// it's a Xordle game, with a single board
+pub fn start_xordle(
+    dictionary: &Dictionary,
+    secret_words: &[Vec<Letter>; 2],
+    puzzle: Puzzle,
+    options: &Options,
+    language: &str,
+    lang: &LanguagePack,
+    stats: &Stats,
+) {
+    let mut session = XordleSession::new(dictionary, secret_words, options.max_tries);
+
+    let mut start = start_json(language, session.length(), 1, puzzle, options);
+    start["xordle"] = true.into();
+    emit(start);
+    for line in read_lines() {
+        for event in session.input(&line) {
+            let message = match &event {
+                MultiEvent::InvalidGuess(reason) => Some(lang.invalid_guess(reason)),
+                _ => None,
+            };
+            emit(complete(event.to_json(), message, lang, stats));
+        }
+        if session.is_over() {
+            return;
+        }
+    }
+}
+
+/// Like [`start`], but every guess is compared against all the secret words
+pub fn start_multi(
+    dictionary: &Dictionary,
+    secret_words: &[Vec<Letter>],
+    puzzle: Puzzle,
+    options: &Options,
+    language: &str,
+    lang: &LanguagePack,
+    stats: &Stats,
+) {
+    let mut session =
+        MultiSession::new(dictionary, secret_words, options.max_tries).with_difficulty(options.difficulty);
+    if let Some(seed) = options.lies {
+        session = session.with_lies(seed);
+    }
+
+    emit(start_json(
+        language,
+        session.length(),
+        secret_words.len(),
+        puzzle,
+        options,
+    ));
+    for line in read_lines() {
+        for event in session.input(&line) {
+            let message = match &event {
+                MultiEvent::InvalidGuess(reason) => Some(lang.invalid_guess(reason)),
+                _ => None,
+            };
+            let mut value = complete(event.to_json(), message, lang, stats);
+            if matches!(event, MultiEvent::Win | MultiEvent::Loss { .. }) && options.lies.is_some() {
+                value["lies"] = session
+                    .boards()
+                    .iter()
+                    .map(|board| lies_json(board.feedback()))
+                    .collect::<Vec<_>>()
+                    .into();
+            }
+            emit(value);
+        }
+        if session.is_over() {
+            return;
+        }
+    }
+}
@@ -1,161 +1,529 @@
-use std::str::FromStr;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-use strum_macros::EnumIter;
+use json::JsonValue;
 
-#[derive(PartialEq, Eq, EnumIter)]
-pub enum Language {
-    English,
-    Spanish,
+use crate::alphabet::Alphabet;
+use crate::ascii::{Folding, FoldingError};
+use crate::dictionary::Dictionary;
+use crate::difficulty::Constraint;
+use crate::session::InvalidGuess;
+
+/// Name of the file describing a language, every language directory must have one
+pub const STRINGS_FILE: &str = "strings.json";
+
+/// Reasons for a language directory to be unusable
+#[derive(Debug)]
+pub enum LanguageError {
+    /// One of its files could not be read
+    Io(PathBuf, std::io::Error),
+    /// Its strings file is not valid json
+    InvalidJson(json::Error),
+    /// Its strings file lacks a required entry
+    Missing(&'static str),
+    /// Its folding file has a line that is not a letter nor two letters
+    InvalidFolding(FoldingError),
 }
 
-impl std::fmt::Display for Language {
+impl std::fmt::Display for LanguageError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(match self {
-            Language::English => "English",
-            Language::Spanish => "Español",
+        match self {
+            LanguageError::Io(path, e) => write!(f, "\"{}\": {}", path.display(), e),
+            LanguageError::InvalidJson(e) => write!(f, "{}: {}", STRINGS_FILE, e),
+            LanguageError::Missing(key) => write!(f, "{}: \"{}\" is missing", STRINGS_FILE, key),
+            LanguageError::InvalidFolding(e) => write!(f, "folding file, {}", e),
+        }
+    }
+}
+
+/// All the texts shown to the player, in one language
+pub struct LanguagePack {
+    /// Rules shown before the first game, with "{length}" for the length of the secret word
+    pub welcome: String,
+    /// The commands and what they do, shown with "h"
+    pub commands: String,
+    /// Shown when the secret word is guessed
+    pub win: String,
+    /// Shown before the secret word when the tries run out
+    pub loss: String,
+    /// Label of the tries left
+    pub tries_left: String,
+    /// The secret word given with "--secret" can't be played, a random one is used instead
+    pub args_invalid_secret_word: String,
+    /// There are no secret words with "{length}" letters
+    pub err_no_secret_words: String,
+    /// There are no two secret words with "{length}" letters and no letter in common, needed by Xordle
+    pub err_no_disjoint_words: String,
+    /// The date given with "--date" can't be read, today's word is used instead
+    pub args_invalid_date: String,
+    /// Title of the daily word, with "{number}" for the puzzle number and "{date}" for its date
+    pub daily_title: String,
+    /// Label of the seed to play the same game again
+    pub seed: String,
+    /// Word before the seed in the shared result
+    pub share_seed: String,
+    /// Label of the games played in the statistics
+    pub stats_played: String,
+    /// Label of the percentage of games won
+    pub stats_win_rate: String,
+    /// Label of the games won in a row up to now
+    pub stats_current_streak: String,
+    /// Label of the most games ever won in a row
+    pub stats_max_streak: String,
+    /// Title of how many games were won with each number of tries
+    pub stats_guess_distribution: String,
+    /// The statistics file could not be written
+    pub err_save_stats: String,
+    /// A word list of the data directory could not be read
+    pub err_read_list: String,
+    /// A language of the data directory could not be loaded
+    pub err_read_language: String,
+    /// Message of [`InvalidGuess::InvalidChars`]
+    pub err_invalid_chars: String,
+    /// Message of [`InvalidGuess::InvalidLength`], with "{length}" for the expected length
+    pub err_invalid_len: String,
+    /// Message of [`InvalidGuess::UnknownWord`]
+    pub err_invalid_word: String,
+    /// Message of [`InvalidGuess::AlreadyFound`]
+    pub err_already_found: String,
+    /// Message of [`InvalidGuess::GameOver`]
+    pub err_game_over: String,
+    /// Message of [`Constraint::MustBeAt`], with "{position}" and "{letter}"
+    pub err_must_be_at: String,
+    /// Message of [`Constraint::MustContain`], with "{letter}"
+    pub err_must_contain: String,
+    /// Message of [`Constraint::MustNotBeAt`], with "{position}" and "{letter}"
+    pub err_must_not_be_at: String,
+    /// Message of [`Constraint::MustNotContain`], with "{letter}"
+    pub err_must_not_contain: String,
+    /// Asks for the next guess
+    pub prompt_guess_title: String,
+    /// Asks if the next game is played in another language
+    pub prompt_ask_change_language: String,
+    /// Asks for the language of the next game
+    pub prompt_change_language: String,
+    /// Asks if another game is played
+    pub prompt_play_again: String,
+    /// Asks for the colors a guess got, in the solver
+    pub prompt_pattern_title: String,
+    /// The colors written in the solver can't be read
+    pub err_invalid_pattern: String,
+    /// How many secret words are still possible, with "{count}"
+    pub solver_candidates: String,
+    /// No secret word is possible with the colors written in the solver
+    pub solver_no_candidates: String,
+    /// What a suggested guess is expected to reveal, with "{bits}"
+    pub solver_entropy: String,
+    /// Label of the first guess of every game in the benchmark
+    pub bench_opening: String,
+    /// Label of the average tries the benchmark needed
+    pub bench_average: String,
+    /// Label of the games the benchmark did not win, with "{tries}" for the tries it had
+    pub bench_failures: String,
+    /// Title of the secret words that needed the most tries in the benchmark
+    pub bench_worst: String,
+    /// Label of the secret words still possible in Absurdle
+    pub absurdle_alive: String,
+    /// Title of the secret words still possible after each guess of an Absurdle game
+    pub absurdle_rounds: String,
+    /// Title of the letters that lied, shown when a Fibble game ends
+    pub fibble_reveal: String,
+    /// Which letter of a row lied, with "{position}"
+    pub fibble_lie: String,
+    /// Which letter of a row lied and the colors it showed and really had, with "{position}", "{shown}" and
+    /// "{was}", for output without colors
+    pub fibble_lie_shown: String,
+    /// Name of the color of a correct letter
+    pub color_correct: String,
+    /// Name of the color of a misplaced letter
+    pub color_misplaced: String,
+    /// Name of the color of an incorrect letter
+    pub color_incorrect: String,
+    /// Shown by "w" before any guess
+    pub no_word_guessed: String,
+    /// How positions are written, like "{n}st" for the ones ending in 1 in english.
+    /// Keys are a whole number after "=", like "=1" for the first one only, the last two digits,
+    /// the last digit or "other", tried in that order
+    ordinals: HashMap<String, String>,
+}
+
+fn get_string(value: &JsonValue, key: &'static str) -> Result<String, LanguageError> {
+    value[key]
+        .as_str()
+        .map(str::to_owned)
+        .ok_or(LanguageError::Missing(key))
+}
+
+impl LanguagePack {
+    /// Reads the "strings" and "ordinals" of a strings file
+    fn from_json(value: &JsonValue) -> Result<Self, LanguageError> {
+        let strings = &value["strings"];
+        Ok(Self {
+            welcome: get_string(strings, "welcome")?,
+            commands: get_string(strings, "commands")?,
+            win: get_string(strings, "win")?,
+            loss: get_string(strings, "loss")?,
+            tries_left: get_string(strings, "tries_left")?,
+            args_invalid_secret_word: get_string(strings, "args_invalid_secret_word")?,
+            err_no_secret_words: get_string(strings, "err_no_secret_words")?,
+            err_no_disjoint_words: get_string(strings, "err_no_disjoint_words")?,
+            args_invalid_date: get_string(strings, "args_invalid_date")?,
+            daily_title: get_string(strings, "daily_title")?,
+            seed: get_string(strings, "seed")?,
+            share_seed: get_string(strings, "share_seed")?,
+            stats_played: get_string(strings, "stats_played")?,
+            stats_win_rate: get_string(strings, "stats_win_rate")?,
+            stats_current_streak: get_string(strings, "stats_current_streak")?,
+            stats_max_streak: get_string(strings, "stats_max_streak")?,
+            stats_guess_distribution: get_string(strings, "stats_guess_distribution")?,
+            err_save_stats: get_string(strings, "err_save_stats")?,
+            err_read_list: get_string(strings, "err_read_list")?,
+            err_read_language: get_string(strings, "err_read_language")?,
+            err_invalid_chars: get_string(strings, "err_invalid_chars")?,
+            err_invalid_len: get_string(strings, "err_invalid_len")?,
+            err_invalid_word: get_string(strings, "err_invalid_word")?,
+            err_already_found: get_string(strings, "err_already_found")?,
+            err_game_over: get_string(strings, "err_game_over")?,
+            err_must_be_at: get_string(strings, "err_must_be_at")?,
+            err_must_contain: get_string(strings, "err_must_contain")?,
+            err_must_not_be_at: get_string(strings, "err_must_not_be_at")?,
+            err_must_not_contain: get_string(strings, "err_must_not_contain")?,
+            prompt_guess_title: get_string(strings, "prompt_guess_title")?,
+            prompt_ask_change_language: get_string(strings, "prompt_ask_change_language")?,
+            prompt_change_language: get_string(strings, "prompt_change_language")?,
+            prompt_play_again: get_string(strings, "prompt_play_again")?,
+            prompt_pattern_title: get_string(strings, "prompt_pattern_title")?,
+            err_invalid_pattern: get_string(strings, "err_invalid_pattern")?,
+            solver_candidates: get_string(strings, "solver_candidates")?,
+            solver_no_candidates: get_string(strings, "solver_no_candidates")?,
+            solver_entropy: get_string(strings, "solver_entropy")?,
+            bench_opening: get_string(strings, "bench_opening")?,
+            bench_average: get_string(strings, "bench_average")?,
+            bench_failures: get_string(strings, "bench_failures")?,
+            bench_worst: get_string(strings, "bench_worst")?,
+            absurdle_alive: get_string(strings, "absurdle_alive")?,
+            absurdle_rounds: get_string(strings, "absurdle_rounds")?,
+            fibble_reveal: get_string(strings, "fibble_reveal")?,
+            fibble_lie: get_string(strings, "fibble_lie")?,
+            fibble_lie_shown: get_string(strings, "fibble_lie_shown")?,
+            color_correct: get_string(strings, "color_correct")?,
+            color_misplaced: get_string(strings, "color_misplaced")?,
+            color_incorrect: get_string(strings, "color_incorrect")?,
+            no_word_guessed: get_string(strings, "no_word_guessed")?,
+            ordinals: value["ordinals"]
+                .entries()
+                .filter_map(|(key, text)| Some((key.to_owned(), text.as_str()?.to_owned())))
+                .collect(),
         })
     }
+
+    /// Writes a position the way it's read in the language, like "1st" or "1ª"
+    pub fn ordinal(&self, n: usize) -> String {
+        let text = [
+            format!("={}", n),
+            (n % 100).to_string(),
+            (n % 10).to_string(),
+            String::from("other"),
+        ]
+        .iter()
+        .find_map(|key| self.ordinals.get(key))
+        .map_or("{n}", String::as_str);
+        text.replace("{n}", &n.to_string())
+    }
+
+    /// Message explaining why a guess was rejected
+    pub fn invalid_guess(&self, reason: &InvalidGuess) -> String {
+        match reason {
+            InvalidGuess::InvalidChars => self.err_invalid_chars.clone(),
+            InvalidGuess::InvalidLength { expected } => with_length(&self.err_invalid_len, *expected),
+            InvalidGuess::UnknownWord => self.err_invalid_word.clone(),
+            InvalidGuess::AlreadyFound => self.err_already_found.clone(),
+            InvalidGuess::GameOver => self.err_game_over.clone(),
+            InvalidGuess::BrokenConstraint(constraint) => self.broken_constraint(constraint),
+        }
+    }
+
+    /// Message naming the hint a guess did not respect, like "2nd letter must be R"
+    pub fn broken_constraint(&self, constraint: &Constraint) -> String {
+        let (text, position, letter) = match constraint {
+            Constraint::MustBeAt { position, letter } => (&self.err_must_be_at, Some(position), letter),
+            Constraint::MustContain { letter } => (&self.err_must_contain, None, letter),
+            Constraint::MustNotBeAt { position, letter } => (&self.err_must_not_be_at, Some(position), letter),
+            Constraint::MustNotContain { letter } => (&self.err_must_not_contain, None, letter),
+        };
+        let position = position.map(|p| self.ordinal(p + 1)).unwrap_or_default();
+        text.replace("{position}", &position)
+            .replace("{letter}", &letter.to_uppercase())
+    }
+}
+
+/// Some texts mention the length of the secret word, they have a "{length}" placeholder for it
+pub fn with_length(text: &str, length: usize) -> String {
+    text.replace("{length}", &length.to_string())
+}
+
+/// Contents of a word list, borrowed if it comes with the game
+pub type WordList = Cow<'static, [u8]>;
+
+/// Where the word lists of a language are
+enum WordLists {
+    Bundled {
+        dictionary: &'static [u8],
+        secrets: &'static [u8],
+    },
+    Files {
+        dictionary: PathBuf,
+        secrets: PathBuf,
+    },
+}
+
+/// A language the game can be played in: its texts, how its letters are compared and sorted and its word lists
+pub struct Language {
+    /// Name of the language written in it, like "Español"
+    pub name: String,
+    /// Ways to write the language in the arguments, like "en" or "english".
+    /// The first one identifies it in the daily word and the statistics
+    pub codes: Vec<String>,
+    /// Texts shown to the player
+    pub pack: LanguagePack,
+    /// Letters taken as another one, like "á" as "a"
+    pub folding: Folding,
+    /// Order of the letters and layout of the keyboard
+    pub alphabet: Alphabet,
+    word_lists: WordLists,
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
 }
 
-impl FromStr for Language {
-    type Err = ();
+impl Language {
+    fn parse(strings: &str, folding: Option<&str>, word_lists: WordLists) -> Result<Self, LanguageError> {
+        let value = json::parse(strings).map_err(LanguageError::InvalidJson)?;
+        let codes: Vec<String> = value["codes"]
+            .members()
+            .filter_map(|code| code.as_str().map(str::to_lowercase))
+            .collect();
+        if codes.is_empty() {
+            return Err(LanguageError::Missing("codes"));
+        }
+        // Both are optional, without them letters are sorted by their base letter on a QWERTY keyboard
+        let keyboard: Vec<&str> = value["keyboard"].members().filter_map(JsonValue::as_str).collect();
+        let alphabet = Alphabet::new(value["alphabet"].as_str().unwrap_or_default(), &keyboard);
+        let folding = match folding {
+            Some(text) => Folding::parse(text).map_err(LanguageError::InvalidFolding)?,
+            None => Folding::default(),
+        };
+        Ok(Self {
+            name: get_string(&value, "name")?,
+            codes,
+            pack: LanguagePack::from_json(&value)?,
+            folding,
+            alphabet,
+            word_lists,
+        })
+    }
+
+    /// Reads a language from a directory with a strings file, a dictionary, a secret words list and a folding file.
+    /// The strings file names the other files
+    pub fn from_dir(dir: &Path) -> Result<Self, LanguageError> {
+        let read = |path: PathBuf| std::fs::read_to_string(&path).map_err(|e| LanguageError::Io(path, e));
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "english" | "en" => Ok(Language::English),
-            "español" | "es" => Ok(Language::Spanish),
-            _ => Err(()),
+        let strings = read(dir.join(STRINGS_FILE))?;
+        let value = json::parse(&strings).map_err(LanguageError::InvalidJson)?;
+        let file = |key: &'static str| get_string(&value, key).map(|name| dir.join(name));
+
+        let folding = match value["folding"].as_str() {
+            Some(name) => Some(read(dir.join(name))?),
+            None => None,
+        };
+        let word_lists = WordLists::Files {
+            dictionary: file("dictionary")?,
+            secrets: file("secrets")?,
+        };
+        Self::parse(&strings, folding.as_deref(), word_lists)
+    }
+
+    /// The code that identifies the language in the daily word and the statistics
+    pub fn code(&self) -> &str {
+        &self.codes[0]
+    }
+
+    /// Reads the dictionary and the secret words list, in that order
+    pub fn word_lists(&self) -> Result<(WordList, WordList), LanguageError> {
+        let read = |path: &PathBuf| std::fs::read(path).map_err(|e| LanguageError::Io(path.clone(), e));
+        match &self.word_lists {
+            WordLists::Bundled { dictionary, secrets } => Ok((Cow::Borrowed(*dictionary), Cow::Borrowed(*secrets))),
+            WordLists::Files { dictionary, secrets } => Ok((Cow::Owned(read(dictionary)?), Cow::Owned(read(secrets)?))),
         }
     }
+
+    /// The dictionary of the language, with its word lists, folding and alphabet
+    pub fn dictionary(&self) -> Result<Dictionary, LanguageError> {
+        let (dictionary, secrets) = self.word_lists()?;
+        Ok(Dictionary::new(&dictionary, &secrets, self.folding.clone()).with_alphabet(self.alphabet.clone()))
+    }
 }
 
-pub struct LanguagePack<'a> {
-    pub welcome: &'a str,
-    pub commands: &'a str,
-    pub win: &'a str,
-    pub loss: &'a str,
-    pub tries_left: &'a str,
-    pub args_invalid_secret_word: &'a str,
-    pub err_invalid_chars: &'a str,
-    pub err_invalid_len: &'a str,
-    pub err_invalid_word: &'a str,
-    pub prompt_guess_title: &'a str,
-    pub prompt_ask_change_language: &'a str,
-    pub prompt_change_language: &'a str,
-    pub prompt_play_again: &'a str,
-    pub no_word_guessed: &'a str,
+/// Files of a language that comes with the game
+struct Bundled {
+    strings: &'static str,
+    folding: &'static str,
+    dictionary: &'static [u8],
+    secrets: &'static [u8],
 }
 
-impl LanguagePack<'static> {
-    pub const fn english() -> LanguagePack<'static> {
-        LanguagePack {
-            welcome: WELCOME_EN,
-            commands: COMMANDS_EN,
-            win: WIN_EN,
-            loss: LOSS_EN,
-            tries_left: TRIES_LEFT_EN,
-            args_invalid_secret_word: ARGS_INVALID_SECRET_WORD_EN,
-            err_invalid_chars: ERR_INVALID_CHARS_EN,
-            err_invalid_len: ERR_INVALID_LEN_EN,
-            err_invalid_word: ERR_INVALID_WORD_EN,
-            prompt_guess_title: PROMPT_GUESS_TITLE_EN,
-            prompt_ask_change_language: PROMPT_ASK_CHANGE_LANGUAGE_EN,
-            prompt_change_language: PROMPT_LANGUAGE_TITLE_EN,
-            prompt_play_again: PROMPT_PLAY_AGAIN_EN,
-            no_word_guessed: NO_WORD_GUESSED_EN,
+const BUNDLED: [Bundled; 8] = [
+    Bundled {
+        strings: include_str!("../media/english/strings.json"),
+        folding: include_str!("../media/english/folding.txt"),
+        dictionary: include_bytes!("../media/english/dictionary.txt"),
+        secrets: include_bytes!("../media/english/secret-words.txt"),
+    },
+    Bundled {
+        strings: include_str!("../media/spanish/strings.json"),
+        folding: include_str!("../media/spanish/folding.txt"),
+        dictionary: include_bytes!("../media/spanish/diccionario.txt"),
+        secrets: include_bytes!("../media/spanish/palabras-secretas.txt"),
+    },
+    Bundled {
+        strings: include_str!("../media/portuguese/strings.json"),
+        folding: include_str!("../media/portuguese/folding.txt"),
+        dictionary: include_bytes!("../media/portuguese/dicionario.txt"),
+        secrets: include_bytes!("../media/portuguese/palavras-secretas.txt"),
+    },
+    Bundled {
+        strings: include_str!("../media/french/strings.json"),
+        folding: include_str!("../media/french/folding.txt"),
+        dictionary: include_bytes!("../media/french/dictionnaire.txt"),
+        secrets: include_bytes!("../media/french/mots-secrets.txt"),
+    },
+    Bundled {
+        strings: include_str!("../media/german/strings.json"),
+        folding: include_str!("../media/german/folding.txt"),
+        dictionary: include_bytes!("../media/german/woerterbuch.txt"),
+        secrets: include_bytes!("../media/german/geheimwoerter.txt"),
+    },
+    Bundled {
+        strings: include_str!("../media/italian/strings.json"),
+        folding: include_str!("../media/italian/folding.txt"),
+        dictionary: include_bytes!("../media/italian/dizionario.txt"),
+        secrets: include_bytes!("../media/italian/parole-segrete.txt"),
+    },
+    Bundled {
+        strings: include_str!("../media/russian/strings.json"),
+        folding: include_str!("../media/russian/folding.txt"),
+        dictionary: include_bytes!("../media/russian/slovar.txt"),
+        secrets: include_bytes!("../media/russian/sekretnye-slova.txt"),
+    },
+    Bundled {
+        strings: include_str!("../media/greek/strings.json"),
+        folding: include_str!("../media/greek/folding.txt"),
+        dictionary: include_bytes!("../media/greek/lexiko.txt"),
+        secrets: include_bytes!("../media/greek/mystikes-lexeis.txt"),
+    },
+];
+
+/// Every language that can be played, the ones that come with the game and the ones found at runtime
+pub struct Languages {
+    languages: Vec<Language>,
+}
+
+impl Languages {
+    /// The languages that come with the game
+    pub fn bundled() -> Self {
+        let languages = BUNDLED
+            .iter()
+            .map(|bundled| {
+                let word_lists = WordLists::Bundled {
+                    dictionary: bundled.dictionary,
+                    secrets: bundled.secrets,
+                };
+                Language::parse(bundled.strings, Some(bundled.folding), word_lists)
+                    .expect("bundled languages should be valid")
+            })
+            .collect();
+        Self { languages }
+    }
+
+    /// Adds the languages in the directory, each one in its own directory with a strings file.
+    /// A language with the same code as one already added replaces it.
+    /// Returns the directories that could not be read
+    pub fn load_dir(&mut self, dir: &Path) -> Vec<(PathBuf, LanguageError)> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut dirs: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.join(STRINGS_FILE).is_file())
+            .collect();
+        dirs.sort();
+
+        let mut errors = Vec::new();
+        for dir in dirs {
+            match Language::from_dir(&dir) {
+                Ok(language) => self.add(language),
+                Err(e) => errors.push((dir, e)),
+            }
         }
+        errors
     }
 
-    pub const fn spanish() -> LanguagePack<'static> {
-        LanguagePack {
-            welcome: WELCOME_ES,
-            commands: COMMANDS_ES,
-            win: WIN_ES,
-            loss: LOSS_ES,
-            tries_left: TRIES_LEFT_ES,
-            args_invalid_secret_word: ARGS_INVALID_SECRET_WORD_ES,
-            err_invalid_chars: ERR_INVALID_CHARS_ES,
-            err_invalid_len: ERR_INVALID_LEN_ES,
-            err_invalid_word: ERR_INVALID_WORD_ES,
-            prompt_guess_title: PROMPT_GUESS_TITLE_ES,
-            prompt_ask_change_language: PROMPT_ASK_CHANGE_LANGUAGE_ES,
-            prompt_change_language: PROMPT_LANGUAGE_TITLE_ES,
-            prompt_play_again: PROMPT_PLAY_AGAIN_ES,
-            no_word_guessed: NO_WORD_GUESSED_ES,
+    /// Adds the language, replacing the one with the same code if there is one
+    pub fn add(&mut self, language: Language) {
+        match self.languages.iter().position(|l| l.code() == language.code()) {
+            Some(i) => self.languages[i] = language,
+            None => self.languages.push(language),
         }
     }
+
+    /// Finds a language by its name or any of its codes, ignoring case
+    pub fn find(&self, s: &str) -> Option<&Language> {
+        let s = s.to_lowercase();
+        self.languages
+            .iter()
+            .find(|language| language.name.to_lowercase() == s || language.codes.contains(&s))
+    }
+
+    /// The languages in the order they were added
+    pub fn iter(&self) -> std::slice::Iter<'_, Language> {
+        self.languages.iter()
+    }
+
+    /// How many languages can be played
+    pub fn len(&self) -> usize {
+        self.languages.len()
+    }
+
+    /// Checks if no language can be played
+    pub fn is_empty(&self) -> bool {
+        self.languages.is_empty()
+    }
 }
 
-// Constants
-
-// Game
-
-const WELCOME_EN: &str = "\
-Welcome! This is a simple game of discovering a 5 letter secret word. If you're not familiar to the rules they're the next:\n
-When you write a word, the letters of the word will be shown in different colors. Those colors mean the next:
-\x1b[92mGreen\x1b[0m: The letter is in the secret word in that position
-\x1b[93mYellow\x1b[0m: The letter is in the secret word, but in another position
-\x1b[91mRed\x1b[0m: The letter is not in the secret word\n
-You can run the program with the \"-h\" option to get help on some configurations
-You can enter \"h\" when writing a word to see useful commands\n
-Press enter to start ";
-const WELCOME_ES: &str = "\
-Este es un juego en el que tienes que adivinar una palabra secreta de 5 letras. Si no conoces las reglas, son las siguientes:\n
-Cuando escribas una palabra, sus letras serán mostradas en diferentes colores. Los colores significan lo siguiente:
-\x1b[92mVerde\x1b[0m: La letra está en la palabra secreta en esa posición
-\x1b[93mAmarillo\x1b[0m: La letra está en la palabra secreta, pero en otra posición
-\x1b[91mRojo\x1b[0m: La letra no está en la palabra secreta\n
-Puedes ejecutar el programa con la opción \"-h\" para obtener ayuda en algunas configuraciones
-Puedes escribir \"h\" cuando estés ingresando una palabra para consultar comandos útiles\n
-Presiona enter para comenzar ";
-
-const COMMANDS_EN: &str = "\
-w: Show previous guessed word
-l: Show all the words guessed at that point
-a: Show all possible letters, like printing the abecedary, with revealed hints
-t: Show how many tries are left
-h: Show available commands
-q: Quits the game";
-const COMMANDS_ES: &str = "\
-w: Muestra la palabra ingresada anteriormente
-l: Muestra todas las palabras ingresadas hasta ese punto
-a: Muestra todas las letras disponibles, como el abecedario, con las pistas reveladas
-t: Muestra los intentos restantes
-h: Muestra los comandos disponibles
-q: Sale del juego";
-
-const WIN_EN: &str = "Congratulations! You win!";
-const WIN_ES: &str = "Has acertado la palabra ¡Felicidades!";
-
-const LOSS_EN: &str = "You loose! Word was";
-const LOSS_ES: &str = "¡Perdiste! La palabra era";
-
-const TRIES_LEFT_EN: &str = "Tries left";
-const TRIES_LEFT_ES: &str = "Intentos restantes";
-
-const ARGS_INVALID_SECRET_WORD_EN: &str = "Secret word in args is invalid. A random secret word is going to be used instead";
-const ARGS_INVALID_SECRET_WORD_ES: &str = "La palabra secreta en los argumentos es inválida. Una palabra aleatoria será usada en su lugar";
-
-const ERR_INVALID_CHARS_EN: &str = "Guess contains invalid characters";
-const ERR_INVALID_CHARS_ES: &str = "La palabra contiene caracteres inválidos";
-
-const ERR_INVALID_LEN_EN: &str = "Guess must be 5 characters long";
-const ERR_INVALID_LEN_ES: &str = "La palabra debe contener 5 caracteres";
-
-const ERR_INVALID_WORD_EN: &str = "Unrecognized word. Try another one";
-const ERR_INVALID_WORD_ES: &str = "La palabra no ha sido encontrada. Intenta una nueva";
-
-const PROMPT_GUESS_TITLE_EN: &str = "Write your guess:";
-const PROMPT_GUESS_TITLE_ES: &str = "Escribe una palabra:";
-
-const PROMPT_LANGUAGE_TITLE_EN: &str = "Select language:";
-const PROMPT_LANGUAGE_TITLE_ES: &str = "Selecciona idioma:";
-
-const PROMPT_PLAY_AGAIN_EN: &str = "Play again?";
-const PROMPT_PLAY_AGAIN_ES: &str = "¿Quires jugar otra vez?";
-
-const PROMPT_ASK_CHANGE_LANGUAGE_EN: &str = "Change language?";
-const PROMPT_ASK_CHANGE_LANGUAGE_ES: &str = "¿Cambiar idioma?";
-
-const NO_WORD_GUESSED_EN: &str = "No word has been guessed yet";
-const NO_WORD_GUESSED_ES: &str = "No se ha ingresado ninguna palabra todavía";
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ordinals(code: &str, positions: &[usize]) -> Vec<String> {
+        let languages = Languages::bundled();
+        let pack = &languages.find(code).expect("the language is bundled").pack;
+        positions.iter().map(|&n| pack.ordinal(n)).collect()
+    }
+
+    #[test]
+    fn ordinals_match_the_whole_number_then_its_last_digits() {
+        assert_eq!(
+            ordinals("en", &[1, 2, 3, 4, 11, 12, 13, 21, 22, 101, 111]),
+            ["1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "101st", "111th"]
+        );
+        // Only the first one is "re" in french, not every one ending in 1
+        assert_eq!(
+            ordinals("fr", &[1, 2, 11, 21, 101]),
+            ["1re", "2e", "11e", "21e", "101e"]
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Engine behind another wordle clone.
+//!
+//! Everything needed to run a game without a terminal lives here: the word lists ([`Dictionary`]),
+//! the scoring of a guess against the secret word ([`Feedback`], [`Char`], [`CharStatus`]), the languages
+//! and their translated texts ([`Languages`], [`LanguagePack`]) and a game session that does no IO at all
+//! ([`GameSession`]).
+//!
+//! ```no_run
+//! use another_wordle_clone::{random, GameSession, Languages, Session};
+//!
+//! let languages = Languages::bundled();
+//! let spanish = languages.find("es").expect("spanish is bundled");
+//! let dictionary = spanish.dictionary().expect("bundled word lists can be read");
+//! let secret = dictionary
+//!     .get_secret_word(5, &mut random::from_seed(42))
+//!     .expect("there are words with 5 letters");
+//! let mut session = GameSession::new(&dictionary, &secret, 6);
+//!
+//! for event in session.input("casas") {
+//!     println!("{:?}", event);
+//! }
+//! ```
+
+#![warn(missing_docs)]
+
+/// Games without a secret word, like Absurdle
+pub mod absurdle;
+/// Order of the letters of a language and its keyboard
+pub mod alphabet;
+/// Letters taken as another one, so accents don't need to be written
+pub mod ascii;
+/// Plays every secret word with a strategy to measure how good it is
+pub mod bench;
+/// Letters and the colors they get
+pub mod char;
+/// The daily word, the same one for every player on the same day
+pub mod daily;
+/// Word lists of a language
+pub mod dictionary;
+/// Hard and strict difficulty, and the hints they make guesses follow
+pub mod difficulty;
+/// Comparison of guesses to a secret word
+pub mod feedback;
+/// Languages and their translated texts
+pub mod language;
+/// Games with many boards, one secret word each
+pub mod multi;
+mod pattern;
+/// Random numbers that are the same for the same seed, so games can be replayed
+pub mod random;
+/// A game with one secret word, and what every kind of game shares
+pub mod session;
+/// Results of finished games that can be shared without spoiling the secret word
+pub mod share;
+/// Secret words still possible and the guesses that reveal the most
+pub mod solver;
+/// Statistics of the games played
+pub mod stats;
+/// Ways of choosing the next guess, which can be measured with the benchmark
+pub mod strategy;
+/// Games with two secret words on one board, like Xordle
+pub mod xordle;
+
+pub use crate::char::{Char, CharStatus};
+pub use dictionary::Dictionary;
+pub use difficulty::Difficulty;
+pub use feedback::Feedback;
+pub use language::{Language, LanguagePack, Languages};
+pub use session::{Command, Event, GameSession, InvalidGuess, Session};
+pub use stats::{Stats, StatsStore};
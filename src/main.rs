@@ -1,90 +1,446 @@
-use std::io::Write;
 use clap::Parser;
+use colored::Colorize;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
 mod args;
-mod ascii;
-mod char;
-mod dictionary;
-mod feedback;
 mod game;
-mod language;
+mod headless;
+mod paths;
 mod prompt;
 
-use dictionary::Dictionary;
-use language::{Language, LanguagePack};
-
-fn get_language_appropriate<T>(language: &Language, english: T, spanish: T) -> T {
-    match language {
-        Language::English => english,
-        Language::Spanish => spanish,
-    }
-}
-
-fn change_language(language: &Language) -> (LanguagePack, Dictionary) {
-    return (
-        get_language_appropriate(language, LanguagePack::english(), LanguagePack::spanish()),
-        get_language_appropriate(
-            language,
-            Dictionary::new(
-                include_bytes!("../media/english/dictionary.txt"),
-                include_bytes!("../media/english/secret-words.txt")
-            ),
-            Dictionary::new(
-                include_bytes!("../media/spanish/diccionario.txt"),
-                include_bytes!("../media/spanish/palabras-secretas.txt")
-            ),
-        ),
+use another_wordle_clone::absurdle::AbsurdleSession;
+use another_wordle_clone::ascii::Folding;
+use another_wordle_clone::char::{letters, normalize, Letter};
+use another_wordle_clone::language::with_length;
+use another_wordle_clone::session::validate_word;
+use another_wordle_clone::share::Puzzle;
+use another_wordle_clone::{bench, daily, random, strategy};
+use another_wordle_clone::{Dictionary, Language, LanguagePack, Languages, StatsStore};
+use args::Args;
+use game::Outcome;
+
+fn read_list(path: &Path, lang: &LanguagePack) -> Vec<u8> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => text.into_bytes(),
+        Err(e) => {
+            eprintln!("{} \"{}\": {}", lang.err_read_list, path.display(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Text files directly inside the directory, sorted so words are always added in the same order
+fn list_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|extension| extension == "txt"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Builds the dictionary from the lists given in the arguments or the ones of the language,
+/// adding the lists the player put in their data directory
+fn load_dictionary(language: &Language, args: &Args) -> Dictionary {
+    let lang = &language.pack;
+    let (language_dictionary, language_secrets) = match language.word_lists() {
+        Ok(lists) => lists,
+        Err(e) => {
+            eprintln!("{} {}", lang.err_read_list, e);
+            std::process::exit(1);
+        }
+    };
+    let mut dictionary = Dictionary::new(
+        &args
+            .get_dictionary_path()
+            .map_or_else(|| language_dictionary.to_vec(), |path| read_list(path, lang)),
+        &args
+            .get_secrets_path()
+            .map_or_else(|| language_secrets.to_vec(), |path| read_list(path, lang)),
+        if args.get_strict_accents() {
+            Folding::strict()
+        } else {
+            language.folding.clone()
+        },
+    )
+    .with_alphabet(language.alphabet.clone());
+    if let Some(patterns_dir) = paths::patterns_dir(language.code()) {
+        dictionary = dictionary.with_cache_dir(patterns_dir);
+    }
+
+    if let Some(words_dir) = paths::words_dir(language.code()) {
+        for path in list_files(&words_dir) {
+            dictionary.extend(&read_list(&path, lang), &[]);
+        }
+        for path in list_files(&words_dir.join("secrets")) {
+            dictionary.extend(&[], &read_list(&path, lang));
+        }
+    }
+    dictionary
+}
+
+/// The bundled languages and the ones the player put in their data directory.
+/// Languages that could not be read are reported in the language that will be played
+fn load_languages(args: &Args) -> Languages {
+    let mut languages = Languages::bundled();
+    let Some(languages_dir) = paths::languages_dir() else {
+        return languages;
+    };
+    let errors = languages.load_dir(&languages_dir);
+    let lang = &args.get_language(&languages).pack;
+    for (dir, e) in errors {
+        eprintln!("{} \"{}\": {}", lang.err_read_language, dir.display(), e);
+    }
+    languages
+}
+
+/// Returns the secret words, one for each board, and how they were chosen
+fn first_time_secrets(
+    args: &Args,
+    seed: u64,
+    dictionary: &mut Dictionary,
+    language: &Language,
+    lang: &LanguagePack,
+) -> (Option<Vec<Vec<Letter>>>, Puzzle) {
+    let length = args.get_length();
+    let boards = args.get_boards();
+    // A secret word can only be set when playing a single board
+    if boards == 1 {
+        if let Some(secret) = args.get_secret(&mut dictionary.abecedary, lang) {
+            dictionary.insert(secret.concat());
+            return (Some(vec![secret]), Puzzle::Chosen);
+        }
+    }
+    if let Some(date) = args.get_date(lang) {
+        return (
+            dictionary.get_daily_words(length, boards, date, language.code()),
+            Puzzle::Daily(daily::puzzle_number(date)),
+        );
+    }
+    random_secrets(dictionary, length, boards, seed)
+}
+
+fn random_secrets(
+    dictionary: &Dictionary,
+    length: usize,
+    boards: usize,
+    seed: u64,
+) -> (Option<Vec<Vec<Letter>>>, Puzzle) {
+    (
+        dictionary.get_secret_words(length, boards, &mut random::from_seed(seed)),
+        Puzzle::Seeded(seed),
+    )
+}
+
+fn print_daily_title(number: i64, lang: &LanguagePack) {
+    println!(
+        "{}\n",
+        lang.daily_title
+            .replace("{number}", &number.to_string())
+            .replace("{date}", &daily::date(number).to_string())
+            .bold()
     );
 }
 
-fn first_time_secret(args_secret: Option<Vec<char>>, dictionary: &mut Dictionary) -> Vec<char> {
-    if let Some(secret) = args_secret {
-        dictionary.dictionary.insert(secret.iter().collect());
-        secret
+/// A game that dodges the guesses with the difficulty of the arguments, none if there are no secret words to dodge with
+fn absurdle_session<'a>(args: &Args, dictionary: &'a Dictionary, lang: &LanguagePack) -> Option<AbsurdleSession<'a>> {
+    let session = AbsurdleSession::new(dictionary, args.get_length());
+    if session.is_none() {
+        eprintln!("{}", with_length(&lang.err_no_secret_words, args.get_length()));
+    }
+    Some(session?.with_difficulty(args.get_difficulty()))
+}
+
+/// Two secret words without letters in common chosen with the seed, none if there are no such words
+fn xordle_secrets(dictionary: &Dictionary, length: usize, seed: u64, lang: &LanguagePack) -> Option<[Vec<Letter>; 2]> {
+    let secret_words = dictionary.get_disjoint_secret_words(length, &mut random::from_seed(seed));
+    if secret_words.is_none() {
+        eprintln!("{}", with_length(&lang.err_no_disjoint_words, length));
+    }
+    secret_words
+}
+
+/// Plays a single game reading from stdin and writing json, see [`headless::start`]
+fn play_json(args: &Args, dictionary: &mut Dictionary, language: &Language, stats: &StatsStore) {
+    let length = args.get_length();
+    let boards = args.get_boards();
+    let seed = args.get_seed();
+    if args.get_absurdle() {
+        let Some(session) = absurdle_session(args, dictionary, &language.pack) else {
+            std::process::exit(1);
+        };
+        let game_stats = stats.get(language.code(), length, boards);
+        headless::start_absurdle(
+            session,
+            &args.get_options(seed),
+            language.code(),
+            &language.pack,
+            &game_stats,
+        );
+        return;
+    }
+    if args.get_xordle() {
+        let Some(secret_words) = xordle_secrets(dictionary, length, seed, &language.pack) else {
+            std::process::exit(1);
+        };
+        let game_stats = stats.get(language.code(), length, boards);
+        headless::start_xordle(
+            dictionary,
+            &secret_words,
+            Puzzle::Seeded(seed),
+            &args.get_options(seed),
+            language.code(),
+            &language.pack,
+            &game_stats,
+        );
+        return;
+    }
+    let (secret_words, puzzle) = first_time_secrets(args, seed, dictionary, language, &language.pack);
+    let Some(secret_words) = secret_words else {
+        eprintln!("{}", with_length(&language.pack.err_no_secret_words, length));
+        std::process::exit(1);
+    };
+    let game_stats = stats.get(language.code(), length, boards);
+    if boards == 1 {
+        headless::start(
+            dictionary,
+            &secret_words[0],
+            puzzle,
+            &args.get_options(seed),
+            language.code(),
+            &language.pack,
+            &game_stats,
+        );
     } else {
-        dictionary.get_secret_word()
+        headless::start_multi(
+            dictionary,
+            &secret_words,
+            puzzle,
+            &args.get_options(seed),
+            language.code(),
+            &language.pack,
+            &game_stats,
+        );
+    }
+}
+
+/// How many of the words that needed the most guesses are shown
+const WORST_WORDS: usize = 10;
+
+/// Plays every secret word with the strategy and prints how it went, see [`bench::run`]
+fn run_bench(args: &Args, dictionary: &Dictionary, strategy: &str, opening: Option<&str>, lang: &LanguagePack) {
+    let length = args.get_length();
+    let opening = opening.map(|opening| normalize(&opening.to_lowercase()));
+    if let Some(opening) = &opening {
+        if let Err(reason) = validate_word(dictionary, length, opening) {
+            eprintln!("{}", lang.invalid_guess(&reason));
+            std::process::exit(1);
+        }
+    }
+    let mut strategy = strategy::from_name(strategy, args.get_seed()).expect("strategy names are checked by clap");
+    let report = bench::run(
+        dictionary,
+        length,
+        strategy.as_mut(),
+        opening.as_deref().map(letters).as_deref(),
+    );
+    if report.games.is_empty() {
+        eprintln!("{}", with_length(&lang.err_no_secret_words, length));
+        std::process::exit(1);
+    }
+
+    let tries = args.get_tries();
+    println!("{}: {}", lang.bench_opening.bold(), report.opening.to_uppercase());
+    println!("{}: {:.3}", lang.bench_average.bold(), report.average());
+    println!(
+        "{}: {}/{}\n",
+        lang.bench_failures.replace("{tries}", &tries.to_string()).bold(),
+        report.failures(tries),
+        report.games.len()
+    );
+    println!("{}", lang.bench_worst.bold());
+    for game in report.worst(WORST_WORDS) {
+        let guesses = game
+            .guesses
+            .map_or_else(|| String::from("X"), |guesses| guesses.to_string());
+        println!("  {} {}", game.secret.to_uppercase(), guesses);
+    }
+    println!();
+}
+
+/// Records a game won or lost and saves the statistics. A game quit before any guess is not recorded,
+/// so no statistics are created for it
+fn record_game(stats: &mut StatsStore, language: &Language, length: usize, boards: usize, outcome: Outcome) {
+    match outcome {
+        Outcome::Won(tries) => stats.get_mut(language.code(), length, boards).record_win(tries),
+        Outcome::Lost => stats.get_mut(language.code(), length, boards).record_loss(),
+        Outcome::Unplayed => return,
+    }
+    if let Err(e) = stats.save() {
+        eprintln!("{}: {}\n", language.pack.err_save_stats, e);
+    }
+}
+
+fn load_stats() -> StatsStore {
+    match paths::stats_file() {
+        Some(path) => StatsStore::load(&path),
+        None => StatsStore::in_memory(),
+    }
+}
+
+/// The text without the escape codes that color it, like "\u{1b}[92m"
+fn strip_colors(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            stripped.push(c);
+        }
     }
+    stripped
 }
 
+/// Waits for the player to press enter. Without a terminal there is no one to wait for,
+/// and the line read would be the first guess, so it is only printed
 fn print_welcome(welcome: &str) {
+    if !prompt::is_interactive() {
+        println!("{}\n", strip_colors(welcome).trim_end());
+        return;
+    }
     print!("{}", welcome);
     std::io::stdout().flush().expect("Could not flush stdout");
-    std::io::stdin().read_line(&mut String::new()).expect("Could not read line");
+    std::io::stdin()
+        .read_line(&mut String::new())
+        .expect("Could not read line");
     println!();
 }
 
-
 fn main() {
-    let args = args::Args::parse();
+    let args = Args::parse();
+    if !std::io::stdout().is_terminal() {
+        colored::control::set_override(false);
+    }
 
-    let mut language = args.get_language();
-    let (mut language_pack, mut dictionary) = change_language(&language);
+    let languages = load_languages(&args);
+    let mut language = args.get_language(&languages);
+    let mut dictionary = load_dictionary(language, &args);
 
-    let mut secret_word: Vec<char>;
+    let length = args.get_length();
+    let boards = args.get_boards();
+    let mut stats = load_stats();
+    if let Some(args::Command::Stats) = args.get_command() {
+        game::print_stats(&stats.get(language.code(), length, boards), &language.pack);
+        return;
+    }
+    if let Some(args::Command::Bench { strategy, opening }) = args.get_command() {
+        run_bench(&args, &dictionary, strategy, opening.as_deref(), &language.pack);
+        return;
+    }
+    if let Some(args::Command::Solve { fibble }) = args.get_command() {
+        game::solve(&dictionary, length, *fibble, &language.pack);
+        return;
+    }
+    if args.get_json() {
+        play_json(&args, &mut dictionary, language, &stats);
+        return;
+    }
+
+    let mut seed = args.get_seed();
     let mut first_time = true;
     loop {
-        secret_word = if first_time {
-            print_welcome(language_pack.welcome);
+        if args.get_absurdle() {
+            if first_time && !args.get_no_welcome() {
+                print_welcome(&with_length(&language.pack.welcome, length));
+            }
             first_time = false;
-            first_time_secret(args.get_secret(&mut dictionary.abecedary, &language_pack), &mut dictionary)
+            let Some(session) = absurdle_session(&args, &dictionary, &language.pack) else {
+                std::process::exit(1);
+            };
+            let game_stats = stats.get(language.code(), length, boards);
+            game::start_absurdle(session, &args.get_options(seed), &language.pack, &game_stats);
+        } else if args.get_xordle() {
+            if first_time && !args.get_no_welcome() {
+                print_welcome(&with_length(&language.pack.welcome, length));
+            }
+            first_time = false;
+            let Some(secret_words) = xordle_secrets(&dictionary, length, seed, &language.pack) else {
+                std::process::exit(1);
+            };
+            let game_stats = stats.get(language.code(), length, boards);
+            game::start_xordle(
+                &dictionary,
+                &secret_words,
+                Puzzle::Seeded(seed),
+                &args.get_options(seed),
+                &language.to_string(),
+                &language.pack,
+                &game_stats,
+            );
+            println!("{}: {}\n", language.pack.seed.bold(), seed);
+            seed = random::next_seed(seed);
         } else {
-            dictionary.get_secret_word()
-        };
+            let (secret_words, puzzle) = if first_time {
+                if !args.get_no_welcome() {
+                    print_welcome(&with_length(&language.pack.welcome, length));
+                }
+                first_time = false;
+                first_time_secrets(&args, seed, &mut dictionary, language, &language.pack)
+            } else {
+                random_secrets(&dictionary, length, boards, seed)
+            };
+            let Some(secret_words) = secret_words else {
+                eprintln!("{}", with_length(&language.pack.err_no_secret_words, length));
+                std::process::exit(1);
+            };
+            if let Puzzle::Daily(number) = puzzle {
+                print_daily_title(number, &language.pack);
+            }
 
-        game::start(
-            &dictionary.dictionary,
-            &secret_word,
-            &dictionary.abecedary,
-            args.get_tries(),
-            &language_pack,
-        );
+            let game_stats = stats.get(language.code(), length, boards);
+            let outcome = if boards == 1 {
+                game::start(
+                    &dictionary,
+                    &secret_words[0],
+                    puzzle,
+                    &args.get_options(seed),
+                    &language.to_string(),
+                    &language.pack,
+                    &game_stats,
+                )
+            } else {
+                game::start_multi(
+                    &dictionary,
+                    &secret_words,
+                    puzzle,
+                    &args.get_options(seed),
+                    &language.to_string(),
+                    &language.pack,
+                    &game_stats,
+                )
+            };
+            // Games with lies are not like the others, they are left out of the statistics
+            if !args.get_fibble() {
+                record_game(&mut stats, language, length, boards, outcome);
+            }
+            // The seed also chooses the lies, so a daily or chosen puzzle with lies can be played again
+            if matches!(puzzle, Puzzle::Seeded(_)) || args.get_fibble() {
+                println!("{}: {}\n", language.pack.seed.bold(), seed);
+            }
+            seed = random::next_seed(seed);
+        }
 
-        if !prompt::play_again(&language_pack) {
+        if args.get_once() || !prompt::play_again(&language.pack) {
             return;
         }
-        if prompt::ask_change_language(&language_pack) {
-            language = prompt::change_language(&language_pack);
-            (language_pack, dictionary) = change_language(&language);
+        if prompt::ask_change_language(&language.pack) {
+            language = prompt::change_language(&languages, language);
+            dictionary = load_dictionary(language, &args);
             first_time = true;
         }
 
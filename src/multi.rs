@@ -0,0 +1,356 @@
+use json::JsonValue;
+
+use crate::char::{json_word, letters, normalize, Char, Letter};
+use crate::dictionary::Dictionary;
+use crate::difficulty::{self, Difficulty};
+use crate::feedback::Feedback;
+use crate::random;
+use crate::session::{event_json, suggest, suggestions_json, validate_word, Command, InvalidGuess, Session};
+use crate::solver::Suggestions;
+
+/// One of the secret words of a multi board game, with its own feedback
+pub struct Board {
+    pub(crate) feedback: Feedback,
+    /// The try in which the secret word was guessed, none if it has not been guessed yet
+    pub(crate) solved_at: Option<u8>,
+}
+
+impl Board {
+    pub(crate) fn new(feedback: Feedback) -> Self {
+        Self {
+            feedback,
+            solved_at: None,
+        }
+    }
+
+    /// The guesses compared to the secret word of the board and what they revealed
+    pub fn feedback(&self) -> &Feedback {
+        &self.feedback
+    }
+
+    /// The try in which the secret word was guessed, none if it has not been guessed yet
+    pub fn solved_at(&self) -> Option<u8> {
+        self.solved_at
+    }
+
+    /// Checks if the secret word of the board was guessed
+    pub fn is_solved(&self) -> bool {
+        self.solved_at.is_some()
+    }
+}
+
+/// Everything that can happen during a multi board session, like [`crate::session::Event`] but for many boards
+#[derive(Clone, Debug, PartialEq)]
+pub enum MultiEvent {
+    /// The guess was rejected, no try was spent
+    InvalidGuess(InvalidGuess),
+    /// The guess was compared to every secret word. There is a row for each board, none for the
+    /// boards that were already solved before this guess
+    Scored(Vec<Option<Vec<Char>>>),
+    /// The secret word of the board was guessed
+    Solved {
+        /// Index of the board, starting at 0
+        board: usize,
+        /// The secret word of the board
+        secret: String,
+    },
+    /// Every secret word was guessed
+    Win,
+    /// No tries are left, the secret words of the boards not solved are given
+    Loss {
+        /// Index of each board not solved with its secret word
+        secrets: Vec<(usize, String)>,
+    },
+    /// Tries left after the last guess, or when asked for
+    TriesLeft(u8),
+    /// The last word guessed on each board, none if nothing has been guessed yet
+    PreviousWord(Option<Vec<Option<Vec<Char>>>>),
+    /// All the words guessed so far on each board
+    History(Vec<Vec<Vec<Char>>>),
+    /// The abecedary of each board with the hints revealed so far
+    Abecedary(Vec<Vec<Char>>),
+    /// The suggestions for each board, none for the boards already solved
+    Suggestions(Vec<Option<Suggestions>>),
+    /// The player asked for their statistics, they are kept by the front end
+    Statistics,
+    /// The player asked for the available commands
+    Help,
+    /// The player quit, the session is over
+    Quit,
+}
+
+/// Rows of every board, null for the boards without one
+fn json_rows(rows: &[Option<Vec<Char>>]) -> JsonValue {
+    rows.iter()
+        .map(|row| row.as_deref().map(json_word))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+impl MultiEvent {
+    /// The event as a json object, like [`crate::session::Event::to_json`]. Boards start at 0
+    pub fn to_json(&self) -> JsonValue {
+        match self {
+            MultiEvent::InvalidGuess(reason) => event_json("invalid_guess", reason.to_json()),
+            MultiEvent::Scored(rows) => event_json("scored", json::object! { boards: json_rows(rows) }),
+            MultiEvent::Solved { board, secret } => {
+                event_json("solved", json::object! { board: *board, secret: secret.as_str() })
+            }
+            MultiEvent::Win => event_json("win", JsonValue::new_object()),
+            MultiEvent::Loss { secrets } => event_json(
+                "loss",
+                json::object! {
+                    secrets: secrets
+                        .iter()
+                        .map(|(board, secret)| json::object! { board: *board, secret: secret.as_str() })
+                        .collect::<Vec<_>>(),
+                },
+            ),
+            MultiEvent::TriesLeft(tries_left) => event_json("tries_left", json::object! { tries_left: *tries_left }),
+            MultiEvent::PreviousWord(rows) => event_json(
+                "previous_word",
+                json::object! { boards: rows.as_deref().map(json_rows) },
+            ),
+            MultiEvent::History(boards) => event_json(
+                "history",
+                json::object! {
+                    boards: boards
+                        .iter()
+                        .map(|history| history.iter().map(|word| json_word(word)).collect::<Vec<_>>())
+                        .collect::<Vec<_>>(),
+                },
+            ),
+            MultiEvent::Abecedary(abecedaries) => event_json(
+                "abecedary",
+                json::object! { boards: abecedaries.iter().map(|abecedary| json_word(abecedary)).collect::<Vec<_>>() },
+            ),
+            MultiEvent::Suggestions(boards) => event_json(
+                "suggestions",
+                json::object! {
+                    boards: boards
+                        .iter()
+                        .map(|suggestions| suggestions.as_ref().map(suggestions_json))
+                        .collect::<Vec<_>>(),
+                },
+            ),
+            MultiEvent::Statistics => event_json("statistics", JsonValue::new_object()),
+            MultiEvent::Help => event_json("help", JsonValue::new_object()),
+            MultiEvent::Quit => event_json("quit", JsonValue::new_object()),
+        }
+    }
+}
+
+/// A game where every guess is compared against many secret words at once, each one on its own board.
+/// It's won when every board is solved. Like [`crate::GameSession`], it does no IO
+pub struct MultiSession<'a> {
+    dictionary: &'a Dictionary,
+    boards: Vec<Board>,
+    length: usize,
+    tries: u8,
+    max_tries: u8,
+    difficulty: Difficulty,
+    quit: bool,
+}
+
+impl<'a> MultiSession<'a> {
+    /// All secret words must have the same length
+    pub fn new(dictionary: &'a Dictionary, secret_words: &[Vec<Letter>], max_tries: u8) -> Self {
+        Self {
+            dictionary,
+            boards: secret_words
+                .iter()
+                .map(|secret_word| {
+                    Board::new(Feedback::new(
+                        secret_word,
+                        &dictionary.abecedary,
+                        &dictionary.folding,
+                        &dictionary.alphabet,
+                    ))
+                })
+                .collect(),
+            length: secret_words.first().map(Vec::len).unwrap_or_default(),
+            tries: 0,
+            max_tries,
+            difficulty: Difficulty::Normal,
+            quit: false,
+        }
+    }
+
+    /// Sets how much the hints revealed restrict the next guesses, every board not solved is checked
+    pub fn with_difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
+    /// Makes one letter of each row of every board lie, see [`Feedback::with_lies`].
+    /// Each board gets its own seed from the one given, so they don't lie in the same letters
+    pub fn with_lies(mut self, mut seed: u64) -> Self {
+        self.boards = self
+            .boards
+            .into_iter()
+            .map(|board| {
+                let feedback = board.feedback.with_lies(random::from_seed(seed));
+                seed = random::next_seed(seed);
+                Board { feedback, ..board }
+            })
+            .collect();
+        self
+    }
+
+    /// Last row of each board, none for the boards solved before the last guess
+    fn last_rows(&self) -> Vec<Option<Vec<Char>>> {
+        self.boards
+            .iter()
+            .map(|board| match board.solved_at {
+                Some(solved_at) if solved_at < self.tries => None,
+                _ => Some(board.feedback.get_guess().to_vec()),
+            })
+            .collect()
+    }
+
+    /// Checks the guess could be played, without comparing it to the secret words
+    pub fn validate(&self, guess: &str) -> Result<(), InvalidGuess> {
+        validate_word(self.dictionary, self.length, guess)?;
+        let guess = letters(guess);
+        self.boards
+            .iter()
+            .filter(|board| !board.is_solved())
+            .try_for_each(|board| {
+                difficulty::check(
+                    self.difficulty,
+                    board.feedback.get_history(),
+                    &guess,
+                    &self.dictionary.folding,
+                )
+            })
+            .map_err(InvalidGuess::BrokenConstraint)
+    }
+
+    // Getters
+
+    /// The boards, in the order of the secret words
+    pub fn boards(&self) -> &[Board] {
+        &self.boards
+    }
+
+    /// Letters of the secret words
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Guesses scored so far
+    pub fn tries(&self) -> u8 {
+        self.tries
+    }
+
+    /// Guesses that can be scored before the game is lost
+    pub fn max_tries(&self) -> u8 {
+        self.max_tries
+    }
+
+    /// Guesses that can still be scored
+    pub fn tries_left(&self) -> u8 {
+        self.max_tries.saturating_sub(self.tries)
+    }
+
+    /// How much the hints revealed restrict the next guesses
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Checks if every secret word was guessed
+    pub fn is_won(&self) -> bool {
+        self.boards.iter().all(Board::is_solved)
+    }
+
+    /// Checks if the tries ran out before guessing every secret word
+    pub fn is_lost(&self) -> bool {
+        !self.is_won() && self.tries_left() == 0
+    }
+
+    /// Checks if the game is won, lost or quit
+    pub fn is_over(&self) -> bool {
+        self.quit || self.is_won() || self.is_lost()
+    }
+}
+
+impl Session for MultiSession<'_> {
+    type Event = MultiEvent;
+
+    fn command(&mut self, command: Command) -> Self::Event {
+        match command {
+            Command::PreviousWord => MultiEvent::PreviousWord((self.tries > 0).then(|| self.last_rows())),
+            Command::History => MultiEvent::History(
+                self.boards
+                    .iter()
+                    .map(|board| board.feedback.get_history().clone())
+                    .collect(),
+            ),
+            Command::Abecedary => {
+                MultiEvent::Abecedary(self.boards.iter().map(|board| board.feedback.get_abecedary()).collect())
+            }
+            Command::TriesLeft => MultiEvent::TriesLeft(self.tries_left()),
+            Command::Suggest => MultiEvent::Suggestions(
+                self.boards
+                    .iter()
+                    .map(|board| {
+                        (!board.is_solved())
+                            .then(|| suggest(self.dictionary, self.length, self.difficulty, &board.feedback))
+                    })
+                    .collect(),
+            ),
+            Command::Statistics => MultiEvent::Statistics,
+            Command::Help => MultiEvent::Help,
+            Command::Quit => {
+                self.quit = true;
+                MultiEvent::Quit
+            }
+        }
+    }
+
+    /// Compares the guess to the secret word of every board not solved yet. A scored guess is followed by
+    /// the boards solved with it and then by a win, a loss or the tries left
+    fn guess(&mut self, guess: &str) -> Vec<Self::Event> {
+        if self.is_over() {
+            return vec![MultiEvent::InvalidGuess(InvalidGuess::GameOver)];
+        }
+        if let Err(reason) = self.validate(guess) {
+            return vec![MultiEvent::InvalidGuess(reason)];
+        }
+
+        self.tries += 1;
+        let mut solved = Vec::new();
+        for (i, board) in self.boards.iter_mut().enumerate() {
+            if board.is_solved() {
+                continue;
+            }
+            board.feedback.compare(normalize(guess));
+            if board.feedback.win {
+                board.solved_at = Some(self.tries);
+                solved.push(MultiEvent::Solved {
+                    board: i,
+                    secret: board.feedback.get_secret(),
+                });
+            }
+        }
+
+        let mut events = vec![MultiEvent::Scored(self.last_rows())];
+        events.append(&mut solved);
+        events.push(if self.is_won() {
+            MultiEvent::Win
+        } else if self.is_lost() {
+            MultiEvent::Loss {
+                secrets: self
+                    .boards
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, board)| !board.is_solved())
+                    .map(|(i, board)| (i, board.feedback.get_secret()))
+                    .collect(),
+            }
+        } else {
+            MultiEvent::TriesLeft(self.tries_left())
+        });
+        events
+    }
+}
@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+const APP_DIR: &str = "another_wordle_clone";
+
+/// Directory where the player's files are kept, like "~/.local/share/another_wordle_clone" following XDG.
+/// None if the platform has no such directory
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_DIR))
+}
+
+pub fn stats_file() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("stats.json"))
+}
+
+/// Directory with the player's own word lists for the language: every ".txt" file in it adds words that can be
+/// guessed, and every ".txt" file in its "secrets" directory adds secret words
+pub fn words_dir(language: &str) -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("words").join(language))
+}
+
+/// Directory with the player's own languages, each one in its own directory with a "strings.json" file.
+/// A language with the same code as a bundled one replaces it
+pub fn languages_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("languages"))
+}
+
+/// Directory where the solver keeps the patterns it computed for the language, like
+/// "~/.cache/another_wordle_clone/patterns/en". They can be deleted, they are computed again when needed
+pub fn patterns_dir(language: &str) -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join(APP_DIR).join("patterns").join(language))
+}
@@ -0,0 +1,351 @@
+use std::io;
+use std::path::Path;
+
+use rayon::prelude::*;
+
+use crate::char::CharStatus;
+
+/// Colors a guess got, written in base 3 with one digit per letter: 0 for incorrect, 1 for misplaced and
+/// 2 for correct. The first letter is the lowest digit, so "CASAS" scored against "CASOS" is 2 + 2·3 + 2·9 + 0·27 + 2·81
+pub type Pattern = u32;
+
+/// The longest words whose colors fit in a [`Pattern`]
+pub const MAX_LENGTH: usize = 20;
+
+/// First bytes of a matrix file, followed by its version
+const MAGIC: &[u8; 4] = b"AWCP";
+const VERSION: u8 = 1;
+
+fn digit(status: &CharStatus) -> Pattern {
+    match status {
+        CharStatus::Incorrect => 0,
+        CharStatus::Misplaced => 1,
+        CharStatus::Correct => 2,
+    }
+}
+
+pub fn encode(statuses: &[CharStatus]) -> Pattern {
+    statuses
+        .iter()
+        .rev()
+        .fold(0, |pattern, status| pattern * 3 + digit(status))
+}
+
+pub fn decode(mut pattern: Pattern, length: usize) -> Vec<CharStatus> {
+    (0..length)
+        .map(|_| {
+            let status = match pattern % 3 {
+                0 => CharStatus::Incorrect,
+                1 => CharStatus::Misplaced,
+                _ => CharStatus::Correct,
+            };
+            pattern /= 3;
+            status
+        })
+        .collect()
+}
+
+/// How many letters got a different color in the two patterns
+pub fn distance(mut a: Pattern, mut b: Pattern) -> usize {
+    let mut distance = 0;
+    while a > 0 || b > 0 {
+        if a % 3 != b % 3 {
+            distance += 1;
+        }
+        a /= 3;
+        b /= 3;
+    }
+    distance
+}
+
+/// Checks that no letter got a better color in the pattern than in the bound, correct being the best
+/// and incorrect the worst
+pub fn within(mut pattern: Pattern, mut bound: Pattern) -> bool {
+    while pattern > 0 {
+        if pattern % 3 > bound % 3 {
+            return false;
+        }
+        pattern /= 3;
+        bound /= 3;
+    }
+    true
+}
+
+/// How many different patterns words of that length can get
+pub fn count(length: usize) -> usize {
+    3usize.pow(length as u32)
+}
+
+/// Colors of the guess compared to the secret word, with the same rules as [`crate::Feedback::compare`]:
+/// correct letters first, then each remaining letter of the guess is misplaced if it matches a letter of the
+/// secret word not matched yet. Letters must be folded first for "á" to be taken as "a". It allocates nothing,
+/// so it can be used to score whole dictionaries. Words can't be longer than [`MAX_LENGTH`]
+pub fn score<T: PartialEq>(guess: &[T], secret: &[T]) -> Pattern {
+    debug_assert!(guess.len() <= MAX_LENGTH && secret.len() <= MAX_LENGTH);
+    // Bit i is set once the letter i of the secret word was matched
+    let mut used: u32 = 0;
+    for (i, letter) in guess.iter().enumerate() {
+        if secret.get(i) == Some(letter) {
+            used |= 1 << i;
+        }
+    }
+    let mut pattern = 0;
+    let mut power = 1;
+    for (i, letter) in guess.iter().enumerate() {
+        let digit = if secret.get(i) == Some(letter) {
+            2
+        } else if let Some(j) = (0..secret.len()).find(|&j| used & (1 << j) == 0 && &secret[j] == letter) {
+            used |= 1 << j;
+            1
+        } else {
+            0
+        };
+        pattern += digit * power;
+        power *= 3;
+    }
+    pattern
+}
+
+/// Patterns kept with the smallest integer that fits them: a byte is enough for 5 letters
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Cells {
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+/// Bytes of each cell for words of that length
+fn cell_size(length: usize) -> usize {
+    match count(length) {
+        n if n <= 1 << 8 => 1,
+        n if n <= 1 << 16 => 2,
+        _ => 4,
+    }
+}
+
+/// Scores every guess against every secret word, one row per guess, in parallel
+fn fill<C>(guesses: &[Vec<u16>], secrets: &[Vec<u16>]) -> Vec<C>
+where
+    C: Copy + Default + Send + TryFrom<Pattern>,
+{
+    let mut cells = vec![C::default(); guesses.len() * secrets.len()];
+    if secrets.is_empty() {
+        return cells;
+    }
+    cells
+        .par_chunks_mut(secrets.len())
+        .zip(guesses.par_iter())
+        .for_each(|(row, guess)| {
+            for (cell, secret) in row.iter_mut().zip(secrets) {
+                *cell = C::try_from(score(guess, secret)).unwrap_or_default();
+            }
+        });
+    cells
+}
+
+/// The pattern every guess gets against every secret word, computed once so the solver only has to look them up.
+/// Words are given as the ids of their folded letters
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatternMatrix {
+    length: usize,
+    guesses: usize,
+    secrets: usize,
+    cells: Cells,
+}
+
+impl PatternMatrix {
+    /// All the words must have the same length, which can't be longer than [`MAX_LENGTH`]
+    pub fn build(length: usize, guesses: &[Vec<u16>], secrets: &[Vec<u16>]) -> Self {
+        assert!(
+            length <= MAX_LENGTH,
+            "words with more than {} letters can't be scored",
+            MAX_LENGTH
+        );
+        Self {
+            length,
+            guesses: guesses.len(),
+            secrets: secrets.len(),
+            cells: match cell_size(length) {
+                1 => Cells::U8(fill(guesses, secrets)),
+                2 => Cells::U16(fill(guesses, secrets)),
+                _ => Cells::U32(fill(guesses, secrets)),
+            },
+        }
+    }
+
+    pub fn get(&self, guess: usize, secret: usize) -> Pattern {
+        let i = guess * self.secrets + secret;
+        match &self.cells {
+            Cells::U8(cells) => cells[i] as Pattern,
+            Cells::U16(cells) => cells[i] as Pattern,
+            Cells::U32(cells) => cells[i],
+        }
+    }
+
+    /// Writes the matrix with the hash of the words it was built from, so a file built from other words is not read
+    pub fn save(&self, path: &Path, hash: u64) -> io::Result<()> {
+        let mut bytes = Vec::with_capacity(22 + self.guesses * self.secrets * cell_size(self.length));
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.push(self.length as u8);
+        bytes.extend_from_slice(&hash.to_le_bytes());
+        bytes.extend_from_slice(&(self.guesses as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.secrets as u32).to_le_bytes());
+        match &self.cells {
+            Cells::U8(cells) => bytes.extend_from_slice(cells),
+            Cells::U16(cells) => cells
+                .iter()
+                .for_each(|cell| bytes.extend_from_slice(&cell.to_le_bytes())),
+            Cells::U32(cells) => cells
+                .iter()
+                .for_each(|cell| bytes.extend_from_slice(&cell.to_le_bytes())),
+        }
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, bytes)
+    }
+
+    /// Reads a matrix saved with [`PatternMatrix::save`], none if there is no file, it can't be read or it was
+    /// built from other words
+    pub fn load(path: &Path, hash: u64) -> Option<Self> {
+        let bytes = std::fs::read(path).ok()?;
+        let (header, cells) = bytes.split_at_checked(22)?;
+        let (magic, header) = header.split_at(4);
+        if magic != MAGIC || header[0] != VERSION || u64::from_le_bytes(header[2..10].try_into().ok()?) != hash {
+            return None;
+        }
+        let length = header[1] as usize;
+        let guesses = u32::from_le_bytes(header[10..14].try_into().ok()?) as usize;
+        let secrets = u32::from_le_bytes(header[14..18].try_into().ok()?) as usize;
+        let size = cell_size(length);
+        if length > MAX_LENGTH || cells.len() != guesses * secrets * size {
+            return None;
+        }
+        let cells = match size {
+            1 => Cells::U8(cells.to_vec()),
+            2 => Cells::U16(
+                cells
+                    .chunks_exact(2)
+                    .map(|cell| u16::from_le_bytes([cell[0], cell[1]]))
+                    .collect(),
+            ),
+            _ => Cells::U32(
+                cells
+                    .chunks_exact(4)
+                    .map(|cell| u32::from_le_bytes([cell[0], cell[1], cell[2], cell[3]]))
+                    .collect(),
+            ),
+        };
+        Some(Self {
+            length,
+            guesses,
+            secrets,
+            cells,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::alphabet::Alphabet;
+    use crate::ascii::Folding;
+    use crate::char::letters;
+    use crate::feedback::Feedback;
+
+    /// The pattern the game shows for the guess, to check [`score`] gives the same colors
+    fn compared(guess: &str, secret: &str, folding: &Folding) -> Pattern {
+        let abecedary: HashSet<String> = letters(guess).into_iter().chain(letters(secret)).collect();
+        let mut feedback = Feedback::new(&letters(secret), &abecedary, folding, &Alphabet::default());
+        feedback.compare(guess.to_owned());
+        let statuses: Vec<CharStatus> = feedback.get_guess().iter().map(|c| c.status.clone()).collect();
+        encode(&statuses)
+    }
+
+    fn scored(guess: &str, secret: &str, folding: &Folding) -> Pattern {
+        let fold =
+            |word: &str| -> Vec<String> { letters(word).iter().map(|letter| folding.fold_str(letter)).collect() };
+        score(&fold(guess), &fold(secret))
+    }
+
+    #[test]
+    fn score_matches_the_game_with_repeated_letters() {
+        let folding = Folding::default();
+        for (guess, secret) in [
+            ("speed", "abide"),
+            ("abide", "speed"),
+            ("casas", "casos"),
+            ("eerie", "there"),
+            ("llama", "allal"),
+        ] {
+            assert_eq!(
+                scored(guess, secret, &folding),
+                compared(guess, secret, &folding),
+                "{} against {}",
+                guess,
+                secret
+            );
+        }
+        assert_eq!(scored("casas", "casos", &folding), 2 + 2 * 3 + 2 * 9 + 2 * 81);
+        // ABIDE has a single "e", so only the first "e" of SPEED is misplaced
+        assert_eq!(
+            decode(scored("speed", "abide", &folding), 5),
+            [
+                CharStatus::Incorrect,
+                CharStatus::Incorrect,
+                CharStatus::Misplaced,
+                CharStatus::Incorrect,
+                CharStatus::Misplaced
+            ]
+        );
+    }
+
+    #[test]
+    fn score_matches_the_game_with_folded_letters() {
+        let folding = Folding::parse("ñ").unwrap();
+        for (guess, secret) in [("arbol", "árbol"), ("cañas", "canas"), ("ñandu", "andúñ")] {
+            assert_eq!(
+                scored(guess, secret, &folding),
+                compared(guess, secret, &folding),
+                "{} against {}",
+                guess,
+                secret
+            );
+        }
+        assert_eq!(
+            scored("arbol", "árbol", &folding),
+            encode(&vec![CharStatus::Correct; 5])
+        );
+    }
+
+    #[test]
+    fn decode_undoes_encode() {
+        let statuses = [CharStatus::Incorrect, CharStatus::Misplaced, CharStatus::Correct];
+        for length in [1, 5, MAX_LENGTH] {
+            for pattern in [0, 1, count(length) as Pattern / 2, count(length) as Pattern - 1] {
+                assert_eq!(encode(&decode(pattern, length)), pattern);
+            }
+            let row: Vec<CharStatus> = (0..length).map(|i| statuses[i * 7 % 3].clone()).collect();
+            assert_eq!(decode(encode(&row), length), row);
+        }
+    }
+
+    #[test]
+    fn saved_matrix_is_loaded_only_with_its_hash() {
+        let words: Vec<Vec<u16>> = vec![vec![0, 1, 2, 1, 0], vec![2, 2, 3, 4, 0], vec![4, 3, 2, 1, 0]];
+        let matrix = PatternMatrix::build(5, &words, &words[1..]);
+        let path = std::env::temp_dir().join(format!("another_wordle_clone-test-{}.bin", std::process::id()));
+        matrix.save(&path, 42).unwrap();
+        let loaded = PatternMatrix::load(&path, 42);
+        let other_words = PatternMatrix::load(&path, 43);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, Some(matrix.clone()));
+        assert_eq!(other_words, None);
+        assert_eq!(matrix.get(1, 0), encode(&vec![CharStatus::Correct; 5]));
+        assert_eq!(matrix.get(0, 1), score(&words[0], &words[2]));
+    }
+}
@@ -1,7 +1,7 @@
-use std::str::FromStr;
+use std::io::{IsTerminal, Write};
 
-use crate::language::{Language, LanguagePack};
-use strum::IntoEnumIterator;
+use another_wordle_clone::{Language, LanguagePack, Languages};
+use unicode_normalization::UnicodeNormalization;
 
 use promkit::{
     preset::{
@@ -19,6 +19,37 @@ fn bool_from_str(s: &str) -> bool {
     }
 }
 
+/// Checks if both stdin and stdout are terminals. Prompts can't be drawn otherwise,
+/// like when the input is piped or the game runs in a script, so plain lines are read instead
+pub fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+/// Reads a line from stdin without its line break, none if stdin was closed
+pub fn read_line() -> Option<String> {
+    let mut line = String::new();
+    match std::io::stdin().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim_end_matches(['\n', '\r']).to_owned()),
+    }
+}
+
+/// Asks a yes or no question reading lines, until one of them is an answer. A closed stdin is a no
+fn read_confirm(title: &str) -> bool {
+    loop {
+        print!("{} (y/n) ", title);
+        std::io::stdout().flush().expect("Could not flush stdout");
+        let Some(input) = read_line() else {
+            println!();
+            return false;
+        };
+        match input.trim().to_lowercase().as_str() {
+            answer @ ("y" | "yes" | "n" | "no") => return bool_from_str(answer),
+            _ => continue,
+        }
+    }
+}
+
 // Templates
 
 fn readline(title: &str) -> Prompt<preset::readline::render::Renderer> {
@@ -29,10 +60,10 @@ fn readline(title: &str) -> Prompt<preset::readline::render::Renderer> {
         .expect("something went wrong when creating prompt")
 }
 
-fn listbox(title: &str) -> Prompt<preset::listbox::render::Renderer> {
-    Listbox::new(Language::iter())
+fn listbox(title: &str, languages: &Languages) -> Prompt<preset::listbox::render::Renderer> {
+    Listbox::new(languages.iter())
         .title(title)
-        .listbox_lines(2)
+        .listbox_lines(languages.len())
         .prompt()
         .expect("something went wrong when creating prompt")
 }
@@ -45,34 +76,73 @@ fn confirm(title: &str) -> Prompt<preset::readline::render::Renderer> {
 
 // Implementations
 
-pub fn read_input(lang: &LanguagePack) -> String {
-    readline(lang.prompt_guess_title)
-        .run()
-        .expect("error reading user input")
-        .to_lowercase()
+/// Accented letters can come as a letter followed by its accent, they are joined so they are read as one letter.
+/// Without a terminal a plain line is read, none once stdin is closed
+fn read(title: &str) -> Option<String> {
+    let input = if is_interactive() {
+        readline(title).run().expect("error reading user input")
+    } else {
+        read_line()?
+    };
+    Some(input.nfc().collect::<String>().to_lowercase())
+}
+
+pub fn read_input(lang: &LanguagePack) -> Option<String> {
+    read(&lang.prompt_guess_title)
+}
+
+/// Reads the colors a guess got, see [`another_wordle_clone::solver::parse_pattern`]
+pub fn read_pattern(lang: &LanguagePack) -> Option<String> {
+    read(&lang.prompt_pattern_title)
 }
 
-pub fn change_language(lang: &LanguagePack) -> Language {
-    let lang = Language::from_str(
-        listbox(lang.prompt_change_language)
-            .run()
-            .expect("error reading user input")
-            .as_str(),
-    )
-    .expect("language is not valid");
+/// Without a terminal the languages are listed and one is read by its name or code,
+/// the current one is kept if stdin is closed
+pub fn change_language<'a>(languages: &'a Languages, current: &'a Language) -> &'a Language {
+    let lang = &current.pack;
+    if !is_interactive() {
+        println!("{}", lang.prompt_change_language);
+        for language in languages.iter() {
+            println!("  {} ({})", language, language.code());
+        }
+        let language = loop {
+            let Some(input) = read_line() else {
+                break current;
+            };
+            if let Some(language) = languages.find(input.trim()) {
+                break language;
+            }
+        };
+        println!();
+        return language;
+    }
+    let language = languages
+        .find(
+            listbox(&lang.prompt_change_language, languages)
+                .run()
+                .expect("error reading user input")
+                .as_str(),
+        )
+        .expect("language is not valid");
     println!();
-    lang
+    language
 }
 
 pub fn ask_change_language(lang: &LanguagePack) -> bool {
-    let input = confirm(lang.prompt_ask_change_language)
+    if !is_interactive() {
+        return read_confirm(&lang.prompt_ask_change_language);
+    }
+    let input = confirm(&lang.prompt_ask_change_language)
         .run()
         .expect("error reading user input");
     bool_from_str(input.as_str())
 }
 
 pub fn play_again(lang: &LanguagePack) -> bool {
-    let input = confirm(lang.prompt_play_again)
+    if !is_interactive() {
+        return read_confirm(&lang.prompt_play_again);
+    }
+    let input = confirm(&lang.prompt_play_again)
         .run()
         .expect("error reading user input");
     bool_from_str(input.as_str())
@@ -0,0 +1,21 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// The generator used for anything random in a game. Unlike `rand`'s default ones, it's guaranteed to
+/// produce the same numbers for the same seed in every platform and version, so games can be replayed
+pub type GameRng = ChaCha8Rng;
+
+/// The generator for a game, the same seed gives the same game
+pub fn from_seed(seed: u64) -> GameRng {
+    GameRng::seed_from_u64(seed)
+}
+
+/// A seed for when the player did not choose one
+pub fn random_seed() -> u64 {
+    rand::thread_rng().gen()
+}
+
+/// The seed of the game after the one played with the given seed, so a whole run can be replayed from its first seed
+pub fn next_seed(seed: u64) -> u64 {
+    from_seed(seed).gen()
+}
@@ -0,0 +1,551 @@
+use std::str::FromStr;
+
+use json::JsonValue;
+
+use crate::char::{json_word, letters, normalize, Char, CharStatus, Letter};
+use crate::dictionary::Dictionary;
+use crate::difficulty::{self, Constraint, Difficulty};
+use crate::feedback::Feedback;
+use crate::random;
+use crate::solver::{Solver, Suggestion, Suggestions, SUGGESTIONS};
+
+/// Reasons for a guess to be rejected before being compared to the secret word
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvalidGuess {
+    /// The guess has letters that are not in the abecedary
+    InvalidChars,
+    /// The guess does not have the length of the secret word
+    InvalidLength {
+        /// Letters of the secret word
+        expected: usize,
+    },
+    /// The guess is not in the dictionary
+    UnknownWord,
+    /// The guess is a secret word that was already found, only in games with many secret words on one board
+    AlreadyFound,
+    /// The game is already won, lost or quit, so nothing can be guessed anymore
+    GameOver,
+    /// The guess ignores a hint revealed before, only in hard and strict difficulty
+    BrokenConstraint(Constraint),
+}
+
+impl InvalidGuess {
+    /// Name of the reason in json output, the name of its message without "err_", like "invalid_len"
+    pub fn code(&self) -> &'static str {
+        match self {
+            InvalidGuess::InvalidChars => "invalid_chars",
+            InvalidGuess::InvalidLength { .. } => "invalid_len",
+            InvalidGuess::UnknownWord => "invalid_word",
+            InvalidGuess::AlreadyFound => "already_found",
+            InvalidGuess::GameOver => "game_over",
+            InvalidGuess::BrokenConstraint(constraint) => constraint.code(),
+        }
+    }
+
+    /// The code of the reason and what explains it, like the expected length
+    pub fn to_json(&self) -> JsonValue {
+        match self {
+            InvalidGuess::InvalidLength { expected } => json::object! { reason: self.code(), expected: *expected },
+            InvalidGuess::BrokenConstraint(constraint) => constraint.to_json(),
+            _ => json::object! { reason: self.code() },
+        }
+    }
+}
+
+/// Commands the player can enter instead of a guess
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// "w": Show previous guessed word
+    PreviousWord,
+    /// "l": Show all the words guessed at that point
+    History,
+    /// "a": Show the abecedary with revealed hints
+    Abecedary,
+    /// "t": Show how many tries are left
+    TriesLeft,
+    /// "s": Suggest the next guesses, the ones expected to reveal the most
+    Suggest,
+    /// "p": Show the player's statistics
+    Statistics,
+    /// "h": Show available commands
+    Help,
+    /// "q": Quit the game
+    Quit,
+}
+
+impl FromStr for Command {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "w" => Ok(Command::PreviousWord),
+            "l" => Ok(Command::History),
+            "a" => Ok(Command::Abecedary),
+            "t" => Ok(Command::TriesLeft),
+            "s" => Ok(Command::Suggest),
+            "p" => Ok(Command::Statistics),
+            "h" => Ok(Command::Help),
+            "q" => Ok(Command::Quit),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Everything that can happen during a session. A front end only has to render these
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// The guess was rejected, no try was spent
+    InvalidGuess(InvalidGuess),
+    /// The guess was compared to the secret word, each char has its status
+    Scored(Vec<Char>),
+    /// The secret word was guessed
+    Win {
+        /// The secret word as it is written
+        secret: String,
+    },
+    /// No tries are left and the secret word was not guessed
+    Loss {
+        /// The secret word the player did not guess
+        secret: String,
+    },
+    /// Tries left after the last guess, or when asked for
+    TriesLeft(u8),
+    /// Secret words still possible after the last guess, or when asked for, in games where the secret word
+    /// is not chosen until a single one is left
+    Alive(usize),
+    /// The last word guessed, none if nothing has been guessed yet
+    PreviousWord(Option<Vec<Char>>),
+    /// All the words guessed so far
+    History(Vec<Vec<Char>>),
+    /// The abecedary with the hints revealed so far
+    Abecedary(Vec<Char>),
+    /// The secret words still possible and the best next guesses
+    Suggestions(Suggestions),
+    /// The player asked for their statistics, they are kept by the front end
+    Statistics,
+    /// The player asked for the available commands
+    Help,
+    /// The player quit, the session is over
+    Quit,
+}
+
+/// An event as a json object, its name in "event" followed by the fields given
+pub(crate) fn event_json(event: &str, fields: JsonValue) -> JsonValue {
+    let mut value = json::object! { event: event };
+    for (key, field) in fields.entries() {
+        value[key] = field.clone();
+    }
+    value
+}
+
+impl Event {
+    /// The event as a json object, like {"event": "tries_left", "tries_left": 3}, for bots and scripts.
+    /// Positions start at 0
+    pub fn to_json(&self) -> JsonValue {
+        match self {
+            Event::InvalidGuess(reason) => event_json("invalid_guess", reason.to_json()),
+            Event::Scored(word) => event_json(
+                "scored",
+                json::object! {
+                    guess: word.iter().map(|c| c.character.as_str()).collect::<String>(),
+                    letters: json_word(word),
+                },
+            ),
+            Event::Win { secret } => event_json("win", json::object! { secret: secret.as_str() }),
+            Event::Loss { secret } => event_json("loss", json::object! { secret: secret.as_str() }),
+            Event::TriesLeft(tries_left) => event_json("tries_left", json::object! { tries_left: *tries_left }),
+            Event::Alive(candidates) => event_json("alive", json::object! { candidates: *candidates }),
+            Event::PreviousWord(word) => event_json(
+                "previous_word",
+                json::object! { letters: word.as_deref().map(json_word) },
+            ),
+            Event::History(history) => event_json(
+                "history",
+                json::object! { rows: history.iter().map(|word| json_word(word)).collect::<Vec<_>>() },
+            ),
+            Event::Abecedary(abecedary) => event_json("abecedary", json::object! { letters: json_word(abecedary) }),
+            Event::Suggestions(suggestions) => event_json("suggestions", suggestions_json(suggestions)),
+            Event::Statistics => event_json("statistics", JsonValue::new_object()),
+            Event::Help => event_json("help", JsonValue::new_object()),
+            Event::Quit => event_json("quit", JsonValue::new_object()),
+        }
+    }
+}
+
+/// The letter that lied in each row, with the status it showed and the one it really had. Rows without a lie are null
+pub fn lies_json(feedback: &Feedback) -> JsonValue {
+    feedback
+        .get_lies()
+        .iter()
+        .zip(feedback.get_history().iter().zip(feedback.get_true_history()))
+        .map(|(lie, (shown, truth))| match lie {
+            Some(position) => json::object! {
+                position: *position,
+                letter: shown[*position].character.as_str(),
+                shown: shown[*position].status.code(),
+                status: truth[*position].status.code(),
+            },
+            None => JsonValue::Null,
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+fn suggestion_json(suggestion: &Suggestion) -> JsonValue {
+    json::object! {
+        word: suggestion.word.as_str(),
+        entropy: suggestion.entropy,
+        candidate: suggestion.candidate,
+    }
+}
+
+/// The number of candidates, the candidates themselves and the best guesses
+pub(crate) fn suggestions_json(suggestions: &Suggestions) -> JsonValue {
+    json::object! {
+        candidate_count: suggestions.candidates.len(),
+        candidates: suggestions.candidates.clone(),
+        guesses: suggestions.guesses.iter().map(suggestion_json).collect::<Vec<_>>(),
+    }
+}
+
+/// Suggestions for a board with the rows of the feedback, only with guesses that can be played in the difficulty.
+/// If its letters lie, one color of each row is taken as a lie. Only the winning row tells the truth, so
+/// knowing which rows lie gives nothing away
+pub(crate) fn suggest(
+    dictionary: &Dictionary,
+    length: usize,
+    difficulty: Difficulty,
+    feedback: &Feedback,
+) -> Suggestions {
+    let Some(mut solver) = Solver::new(dictionary, length) else {
+        return Suggestions::default();
+    };
+    let history = feedback.get_history();
+    for (row, lie) in history.iter().zip(feedback.get_lies()) {
+        let guess: Vec<Letter> = row.iter().map(|c| c.character.clone()).collect();
+        let pattern: Vec<CharStatus> = row.iter().map(|c| c.status.clone()).collect();
+        if lie.is_some() {
+            solver.update_with_lie(&guess, &pattern);
+        } else {
+            solver.update(&guess, &pattern);
+        }
+    }
+    if difficulty != Difficulty::Normal {
+        solver.retain_guesses(|guess| difficulty::check(difficulty, history, guess, &dictionary.folding).is_ok());
+    }
+    solver.suggestions(SUGGESTIONS)
+}
+
+/// Checks the guess is a word of the dictionary with the right length, the checks every game mode shares
+pub fn validate_word(dictionary: &Dictionary, length: usize, guess: &str) -> Result<(), InvalidGuess> {
+    let guess = letters(guess);
+    if guess.iter().any(|letter| !dictionary.abecedary.contains(letter)) {
+        return Err(InvalidGuess::InvalidChars);
+    }
+    if guess.len() != length {
+        return Err(InvalidGuess::InvalidLength { expected: length });
+    }
+    if !dictionary.contains(&guess.concat()) {
+        return Err(InvalidGuess::UnknownWord);
+    }
+    Ok(())
+}
+
+/// What every kind of game does with the lines the player writes, which can be either a command or a guess
+pub trait Session {
+    /// What can happen during the game, a front end only has to render these
+    type Event;
+
+    /// Handles a command, which never spends a try
+    fn command(&mut self, command: Command) -> Self::Event;
+
+    /// Validates the guess and, if valid, scores it spending one try. Nothing is guessed once the game is over
+    fn guess(&mut self, guess: &str) -> Vec<Self::Event>;
+
+    /// Handles a line written by the player, which can be either a command or a guess.
+    /// Blank lines are ignored
+    fn input(&mut self, input: &str) -> Vec<Self::Event> {
+        let input = normalize(input.trim());
+        if input.is_empty() {
+            return Vec::new();
+        }
+        match Command::from_str(&input) {
+            Ok(command) => vec![self.command(command)],
+            Err(()) => self.guess(&input),
+        }
+    }
+}
+
+/// A single game, from the first guess until the secret word is found, the tries run out or the
+/// player quits. It does not read nor print anything: it takes guesses and commands and returns
+/// the events that happened, so it can be driven by any kind of front end
+pub struct GameSession<'a> {
+    dictionary: &'a Dictionary,
+    feedback: Feedback,
+    /// Letters in the secret word, guesses must have the same
+    length: usize,
+    /// Guesses made so far, only valid guesses count
+    tries: u8,
+    max_tries: u8,
+    difficulty: Difficulty,
+    quit: bool,
+}
+
+impl<'a> GameSession<'a> {
+    /// A game to guess the secret word in at most that many tries, in normal difficulty
+    pub fn new(dictionary: &'a Dictionary, secret_word: &[Letter], max_tries: u8) -> Self {
+        Self {
+            dictionary,
+            feedback: Feedback::new(
+                secret_word,
+                &dictionary.abecedary,
+                &dictionary.folding,
+                &dictionary.alphabet,
+            ),
+            length: secret_word.len(),
+            tries: 0,
+            max_tries,
+            difficulty: Difficulty::Normal,
+            quit: false,
+        }
+    }
+
+    /// Sets how much the hints revealed restrict the next guesses
+    pub fn with_difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
+    /// Makes one letter of each row lie, chosen with the seed, see [`Feedback::with_lies`]
+    pub fn with_lies(mut self, seed: u64) -> Self {
+        self.feedback = self.feedback.with_lies(random::from_seed(seed));
+        self
+    }
+
+    /// Checks the guess could be played, without comparing it to the secret word
+    pub fn validate(&self, guess: &str) -> Result<(), InvalidGuess> {
+        validate_word(self.dictionary, self.length, guess)?;
+        let guess = letters(guess);
+        difficulty::check(
+            self.difficulty,
+            self.feedback.get_history(),
+            &guess,
+            &self.dictionary.folding,
+        )
+        .map_err(InvalidGuess::BrokenConstraint)
+    }
+
+    // Getters
+
+    /// The guesses compared so far and what they revealed
+    pub fn feedback(&self) -> &Feedback {
+        &self.feedback
+    }
+
+    /// Letters of the secret word
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Guesses scored so far
+    pub fn tries(&self) -> u8 {
+        self.tries
+    }
+
+    /// Guesses that can be scored before the game is lost
+    pub fn max_tries(&self) -> u8 {
+        self.max_tries
+    }
+
+    /// How much the hints revealed restrict the next guesses
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Guesses that can still be scored
+    pub fn tries_left(&self) -> u8 {
+        self.max_tries.saturating_sub(self.tries)
+    }
+
+    /// Checks if the secret word was guessed
+    pub fn is_won(&self) -> bool {
+        self.feedback.win
+    }
+
+    /// Checks if the tries ran out before guessing the secret word
+    pub fn is_lost(&self) -> bool {
+        !self.is_won() && self.tries_left() == 0
+    }
+
+    /// Checks if the game is won, lost or quit
+    pub fn is_over(&self) -> bool {
+        self.quit || self.is_won() || self.is_lost()
+    }
+}
+
+impl Session for GameSession<'_> {
+    type Event = Event;
+
+    fn command(&mut self, command: Command) -> Self::Event {
+        match command {
+            Command::PreviousWord => {
+                let guess = self.feedback.get_guess();
+                Event::PreviousWord((!guess.is_empty()).then(|| guess.to_vec()))
+            }
+            Command::History => Event::History(self.feedback.get_history().clone()),
+            Command::Abecedary => Event::Abecedary(self.feedback.get_abecedary()),
+            Command::TriesLeft => Event::TriesLeft(self.tries_left()),
+            Command::Suggest => {
+                Event::Suggestions(suggest(self.dictionary, self.length, self.difficulty, &self.feedback))
+            }
+            Command::Statistics => Event::Statistics,
+            Command::Help => Event::Help,
+            Command::Quit => {
+                self.quit = true;
+                Event::Quit
+            }
+        }
+    }
+
+    /// Compares the guess to the secret word. A scored guess is followed by a win, a loss or the tries left
+    fn guess(&mut self, guess: &str) -> Vec<Self::Event> {
+        if self.is_over() {
+            return vec![Event::InvalidGuess(InvalidGuess::GameOver)];
+        }
+        if let Err(reason) = self.validate(guess) {
+            return vec![Event::InvalidGuess(reason)];
+        }
+
+        self.feedback.compare(normalize(guess));
+        self.tries += 1;
+
+        let mut events = vec![Event::Scored(self.feedback.get_guess().to_vec())];
+        events.push(if self.is_won() {
+            Event::Win {
+                secret: self.feedback.get_secret(),
+            }
+        } else if self.is_lost() {
+            Event::Loss {
+                secret: self.feedback.get_secret(),
+            }
+        } else {
+            Event::TriesLeft(self.tries_left())
+        });
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii::Folding;
+
+    fn dictionary() -> Dictionary {
+        Dictionary::new(b"piano\ncarro\nmango\n", b"perro\nplaza\n", Folding::default())
+    }
+
+    fn session(dictionary: &Dictionary, max_tries: u8) -> GameSession<'_> {
+        GameSession::new(dictionary, &letters("perro"), max_tries)
+    }
+
+    #[test]
+    fn guessing_the_secret_word_wins() {
+        let dictionary = dictionary();
+        let mut session = session(&dictionary, 6);
+        let events = session.input("piano");
+        assert!(matches!(events[..], [Event::Scored(_), Event::TriesLeft(5)]));
+
+        let events = session.input(" perro ");
+        assert!(matches!(events[..], [Event::Scored(_), Event::Win { .. }]));
+        assert_eq!(
+            events[1],
+            Event::Win {
+                secret: String::from("perro")
+            }
+        );
+        assert!(session.is_won());
+        assert_eq!(session.tries(), 2);
+    }
+
+    #[test]
+    fn running_out_of_tries_loses() {
+        let dictionary = dictionary();
+        let mut session = session(&dictionary, 2);
+        session.input("piano");
+        let events = session.input("mango");
+        assert_eq!(
+            events[1],
+            Event::Loss {
+                secret: String::from("perro")
+            }
+        );
+        assert!(session.is_lost());
+        assert_eq!(session.tries_left(), 0);
+    }
+
+    #[test]
+    fn invalid_guesses_do_not_spend_tries() {
+        let dictionary = dictionary();
+        let mut session = session(&dictionary, 6);
+        assert_eq!(
+            session.input("pi4no"),
+            vec![Event::InvalidGuess(InvalidGuess::InvalidChars)]
+        );
+        assert_eq!(
+            session.input("pian"),
+            vec![Event::InvalidGuess(InvalidGuess::InvalidLength { expected: 5 })]
+        );
+        assert_eq!(
+            session.input("rompa"),
+            vec![Event::InvalidGuess(InvalidGuess::UnknownWord)]
+        );
+        assert_eq!(session.tries(), 0);
+    }
+
+    #[test]
+    fn hard_difficulty_rejects_guesses_that_ignore_hints() {
+        let dictionary = dictionary();
+        let mut session = session(&dictionary, 6).with_difficulty(Difficulty::Hard);
+        session.input("piano");
+        let events = session.input("carro");
+        assert!(matches!(
+            events[..],
+            [Event::InvalidGuess(InvalidGuess::BrokenConstraint(_))]
+        ));
+        assert_eq!(session.tries(), 1);
+    }
+
+    #[test]
+    fn guessing_after_the_game_is_over_is_rejected() {
+        let dictionary = dictionary();
+        let mut session = session(&dictionary, 6);
+        session.input("perro");
+        assert_eq!(
+            session.input("piano"),
+            vec![Event::InvalidGuess(InvalidGuess::GameOver)]
+        );
+
+        let mut session = self::session(&dictionary, 6);
+        assert_eq!(session.input("q"), vec![Event::Quit]);
+        assert!(session.is_over());
+        assert_eq!(
+            session.input("piano"),
+            vec![Event::InvalidGuess(InvalidGuess::GameOver)]
+        );
+    }
+
+    #[test]
+    fn commands_are_not_guesses() {
+        let dictionary = dictionary();
+        let mut session = session(&dictionary, 6);
+        assert_eq!(session.input("w"), vec![Event::PreviousWord(None)]);
+        assert_eq!(session.input("T"), vec![Event::TriesLeft(6)]);
+        assert_eq!(session.input("   "), Vec::new());
+        session.input("piano");
+        assert!(matches!(session.input("w")[..], [Event::PreviousWord(Some(_))]));
+        assert!(matches!(&session.input("l")[..], [Event::History(history)] if history.len() == 1));
+        assert_eq!(session.input("p"), vec![Event::Statistics]);
+        assert_eq!(session.input("h"), vec![Event::Help]);
+        assert_eq!(session.tries(), 1);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::char::{Char, CharStatus};
+use crate::difficulty::Difficulty;
+use crate::language::LanguagePack;
+use crate::multi::MultiSession;
+use crate::session::GameSession;
+use crate::xordle::XordleSession;
+
+/// How the secret word of a game was chosen, it tells others which game to play to compare results
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Puzzle {
+    /// The word of the day with that number
+    Daily(i64),
+    /// A random word chosen with that seed
+    Seeded(u64),
+    /// A word chosen by the player, it can't be replayed by others
+    Chosen,
+}
+
+fn square(status: &CharStatus, ascii: bool) -> &'static str {
+    match (status, ascii) {
+        (CharStatus::Correct, false) => "🟩",
+        (CharStatus::Misplaced, false) => "🟨",
+        (CharStatus::Incorrect, false) => "⬛",
+        (CharStatus::Correct, true) => "G",
+        (CharStatus::Misplaced, true) => "Y",
+        (CharStatus::Incorrect, true) => ".",
+    }
+}
+
+/// One line of squares per guess, showing the status of each char but not the char itself
+pub fn grid(history: &[Vec<Char>], ascii: bool) -> String {
+    history
+        .iter()
+        .map(|row| row.iter().map(|c| square(&c.status, ascii)).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn puzzle_text(puzzle: Puzzle, lang: &LanguagePack) -> String {
+    match puzzle {
+        Puzzle::Daily(number) => format!(" #{}", number),
+        Puzzle::Seeded(seed) => format!(" {} {}", lang.share_seed, seed),
+        Puzzle::Chosen => String::new(),
+    }
+}
+
+fn hard_text(difficulty: Difficulty) -> &'static str {
+    if difficulty == Difficulty::Normal {
+        ""
+    } else {
+        "*"
+    }
+}
+
+/// Name of the game, the one with lies is Fibble
+fn title(lies: bool) -> &'static str {
+    if lies {
+        "Fibble"
+    } else {
+        "Wordle"
+    }
+}
+
+/// Result of a finished game that can be shared without spoiling the secret word, like:
+///
+/// ```text
+/// Wordle English #655 3/6*
+/// ⬛🟨⬛⬛⬛
+/// 🟩⬛🟩🟨⬛
+/// 🟩🟩🟩🟩🟩
+/// ```
+///
+/// The score is "X" if the game was lost, and "*" marks games played in hard or strict difficulty.
+/// Games with lies are titled "Fibble" instead. With `ascii` the squares are written as "G", "Y" and "."
+pub fn share(session: &GameSession, language: &str, puzzle: Puzzle, lang: &LanguagePack, ascii: bool) -> String {
+    let score = if session.is_won() {
+        session.tries().to_string()
+    } else {
+        String::from("X")
+    };
+    format!(
+        "{} {}{} {}/{}{}\n{}",
+        title(session.feedback().has_lies()),
+        language,
+        puzzle_text(puzzle, lang),
+        score,
+        session.max_tries(),
+        hard_text(session.difficulty()),
+        grid(session.feedback().get_history(), ascii)
+    )
+}
+
+/// Like [`share`], but for Xordle games, with the colors of both secret words merged
+pub fn share_xordle(
+    session: &XordleSession,
+    language: &str,
+    puzzle: Puzzle,
+    lang: &LanguagePack,
+    ascii: bool,
+) -> String {
+    let score = if session.is_won() {
+        session.tries().to_string()
+    } else {
+        String::from("X")
+    };
+    format!(
+        "Xordle {}{} {}/{}\n{}",
+        language,
+        puzzle_text(puzzle, lang),
+        score,
+        session.max_tries(),
+        grid(session.history(), ascii)
+    )
+}
+
+/// Like [`share`], but for multi board games. The title says how many boards were played, and each board
+/// gets a column of squares that ends when it was solved
+pub fn share_multi(session: &MultiSession, language: &str, puzzle: Puzzle, lang: &LanguagePack, ascii: bool) -> String {
+    let score = if session.is_won() {
+        session.tries().to_string()
+    } else {
+        String::from("X")
+    };
+    let padding = if ascii { " " } else { "  " }.repeat(session.length());
+    let rows: Vec<String> = (0..session.tries() as usize)
+        .map(|try_index| {
+            session
+                .boards()
+                .iter()
+                .map(|board| match board.feedback().get_history().get(try_index) {
+                    Some(row) => row.iter().map(|c| square(&c.status, ascii)).collect(),
+                    None => padding.clone(),
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+        .collect();
+    format!(
+        "{} {}{} x{} {}/{}{}\n{}",
+        title(session.boards().iter().any(|board| board.feedback().has_lies())),
+        language,
+        puzzle_text(puzzle, lang),
+        session.boards().len(),
+        score,
+        session.max_tries(),
+        hard_text(session.difficulty()),
+        rows.join("\n")
+    )
+}
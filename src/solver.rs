@@ -0,0 +1,446 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use rayon::prelude::*;
+
+use crate::char::{letters, CharStatus, Letter};
+use crate::daily::fnv1a;
+use crate::dictionary::Dictionary;
+use crate::pattern::{self, Pattern, PatternMatrix};
+
+/// How many guesses are suggested each time
+pub const SUGGESTIONS: usize = 5;
+
+/// Up to this length the candidates are grouped by their pattern in an array, longer words have too many patterns
+const DENSE_LENGTH: usize = 10;
+
+/// A word as the solver keeps it: its letters as they are written, and the ids of its folded letters,
+/// which are compared the same but much faster
+#[derive(Clone, Debug)]
+pub struct Word {
+    /// The letters as they are written in the word list
+    pub letters: Vec<Letter>,
+    /// One id for each folded letter, the same letters always get the same id in a solver
+    pub ids: Vec<u16>,
+}
+
+impl Word {
+    /// The word as it is written
+    pub fn text(&self) -> String {
+        self.letters.concat()
+    }
+}
+
+/// A word worth guessing next
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    /// The word to guess
+    pub word: String,
+    /// Bits of information the colors of the guess are expected to reveal, the more the fewer candidates are left
+    pub entropy: f64,
+    /// The word could be the secret word, so guessing it could also win
+    pub candidate: bool,
+}
+
+/// What the solver knows at some point of a game
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Suggestions {
+    /// Secret words still possible, sorted
+    pub candidates: Vec<String>,
+    /// Best guesses first
+    pub guesses: Vec<Suggestion>,
+}
+
+/// Reads the colors of a guess, one per letter: "g" or "🟩" for correct, "y" or "🟨" for misplaced and
+/// "." or "⬛" for incorrect, the same ones the result grid uses. Digits from 0 to 2, "x", "r" and "b" are
+/// also read, and spaces are skipped. None if some color is not known
+pub fn parse_pattern(text: &str) -> Option<Vec<CharStatus>> {
+    text.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c.to_lowercase().next()? {
+            'g' | '2' | '🟩' => Some(CharStatus::Correct),
+            'y' | '1' | '🟨' => Some(CharStatus::Misplaced),
+            '.' | '0' | '-' | 'x' | 'r' | 'b' | '⬛' | '⬜' | '🟥' => Some(CharStatus::Incorrect),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The words of a dictionary with one length and the pattern of every guess against every secret word.
+/// It's built once and shared by every solver of the dictionary for that length
+#[derive(Debug)]
+pub(crate) struct Table {
+    length: usize,
+    /// Words that can be guessed, only one of the ones that are folded the same, sorted
+    guesses: Vec<Word>,
+    secrets: Vec<Word>,
+    /// Ids given to the folded letters
+    ids: HashMap<Letter, u16>,
+    /// Index of each guess by the ids of its letters
+    guess_index: HashMap<Vec<u16>, usize>,
+    /// Index of the guess written like each secret word
+    secret_guess: Vec<Option<usize>>,
+    matrix: PatternMatrix,
+}
+
+impl Table {
+    fn new(dictionary: &Dictionary, length: usize) -> Self {
+        let folding = &dictionary.folding;
+        let mut words: Vec<&String> = dictionary.words(length).collect();
+        words.sort();
+        // "árbol" and its folded alias "arbol" are compared the same, the word as it's written is kept
+        let mut unique: HashMap<String, &String> = HashMap::new();
+        for word in words {
+            unique
+                .entry(folding.fold_str(word))
+                .and_modify(|kept| {
+                    if folding.has_foldable(word) {
+                        *kept = word;
+                    }
+                })
+                .or_insert(word);
+        }
+        let mut guesses: Vec<&String> = unique.into_values().collect();
+        guesses.sort();
+
+        let mut ids: HashMap<Letter, u16> = HashMap::new();
+        let mut word = |text: &str| {
+            let letters = letters(text);
+            let word_ids = letters
+                .iter()
+                .map(|letter| {
+                    let next = ids.len() as u16;
+                    *ids.entry(folding.fold_str(letter)).or_insert(next)
+                })
+                .collect();
+            Word { letters, ids: word_ids }
+        };
+        let guesses: Vec<Word> = guesses.into_iter().map(|guess| word(guess)).collect();
+        let secrets: Vec<Word> = dictionary
+            .secret_words(length)
+            .iter()
+            .map(|secret| word(secret))
+            .collect();
+
+        let guess_index: HashMap<Vec<u16>, usize> = guesses
+            .iter()
+            .enumerate()
+            .map(|(i, guess)| (guess.ids.clone(), i))
+            .collect();
+        let secret_guess = secrets
+            .iter()
+            .map(|secret| guess_index.get(&secret.ids).copied())
+            .collect();
+        let matrix = Self::matrix(dictionary, length, &guesses, &secrets);
+        Self {
+            length,
+            guesses,
+            secrets,
+            ids,
+            guess_index,
+            secret_guess,
+            matrix,
+        }
+    }
+
+    /// Reads the matrix from the cache directory of the dictionary if it was built from the same words,
+    /// otherwise it's built and saved there. The file is named after the length and a hash of the words, and
+    /// only the last one of each length is kept: a new one replaces the files of older word lists, so editing
+    /// a dictionary or switching to --strict-accents or --dictionary doesn't leave files behind
+    fn matrix(dictionary: &Dictionary, length: usize, guesses: &[Word], secrets: &[Word]) -> PatternMatrix {
+        let words = guesses.iter().chain(secrets).flat_map(|word| {
+            let len = (word.ids.len() as u16).to_le_bytes();
+            len.into_iter().chain(word.ids.iter().flat_map(|id| id.to_le_bytes()))
+        });
+        let hash = fnv1a(
+            (length as u64)
+                .to_le_bytes()
+                .into_iter()
+                .chain((guesses.len() as u64).to_le_bytes())
+                .chain(words),
+        );
+
+        let prefix = format!("patterns-{}-", length);
+        let file = format!("{}{:016x}.bin", prefix, hash);
+        let path = dictionary.cache_dir.as_ref().map(|dir| dir.join(&file));
+        if let Some(matrix) = path.as_ref().and_then(|path| PatternMatrix::load(path, hash)) {
+            return matrix;
+        }
+        let guess_ids: Vec<Vec<u16>> = guesses.iter().map(|guess| guess.ids.clone()).collect();
+        let secret_ids: Vec<Vec<u16>> = secrets.iter().map(|secret| secret.ids.clone()).collect();
+        let matrix = PatternMatrix::build(length, &guess_ids, &secret_ids);
+        // The cache is only to start faster, if it can't be written the matrix is built again next time
+        if let (Some(dir), Some(path)) = (&dictionary.cache_dir, path) {
+            if matrix.save(&path, hash).is_ok() {
+                remove_stale(dir, &prefix, &file);
+            }
+        }
+        matrix
+    }
+
+    /// Ids of the folded letters of the word, letters no word has get an id that matches nothing
+    fn ids(&self, dictionary: &Dictionary, word: &[Letter]) -> Vec<u16> {
+        word.iter()
+            .map(|letter| {
+                self.ids
+                    .get(&dictionary.folding.fold_str(letter))
+                    .copied()
+                    .unwrap_or(u16::MAX)
+            })
+            .collect()
+    }
+}
+
+/// Removes the cache files of the directory with the prefix, but the one just written
+fn remove_stale(dir: &Path, prefix: &str, file: &str) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with(prefix) && name.ends_with(".bin") && name != file {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// The table of the dictionary for words of that length, built the first time it's needed
+fn table(dictionary: &Dictionary, length: usize) -> Arc<Table> {
+    let mut tables = dictionary.tables.lock().expect("tables should not be poisoned");
+    tables
+        .entry(length)
+        .or_insert_with(|| Arc::new(Table::new(dictionary, length)))
+        .clone()
+}
+
+/// Keeps the secret words consistent with every row scored so far and ranks the guesses by how much
+/// they are expected to reveal about the secret word. Patterns are looked up in a matrix computed once
+/// for the dictionary, so cloning a solver or creating another one is cheap
+#[derive(Clone)]
+pub struct Solver<'a> {
+    dictionary: &'a Dictionary,
+    table: Arc<Table>,
+    /// Indexes of the secret words consistent with every row so far
+    candidates: Vec<usize>,
+    /// Indexes of the words that can be guessed
+    guesses: Vec<usize>,
+}
+
+impl<'a> Solver<'a> {
+    /// Every secret word with the length is a candidate, and every word of the dictionary with it can be guessed.
+    /// None if the length is longer than 20 letters, the colors of such words can't be scored
+    pub fn new(dictionary: &'a Dictionary, length: usize) -> Option<Self> {
+        if length > pattern::MAX_LENGTH {
+            return None;
+        }
+        let table = table(dictionary, length);
+        Some(Self {
+            dictionary,
+            candidates: (0..table.secrets.len()).collect(),
+            guesses: (0..table.guesses.len()).collect(),
+            table,
+        })
+    }
+
+    /// The pattern each candidate would give the guess, in the same order as the candidates
+    fn patterns(&self, guess: &[Letter]) -> Vec<Pattern> {
+        let ids = self.table.ids(self.dictionary, guess);
+        let table = &self.table;
+        match table.guess_index.get(&ids) {
+            Some(&guess) => self
+                .candidates
+                .iter()
+                .map(|&candidate| table.matrix.get(guess, candidate))
+                .collect(),
+            None => self
+                .candidates
+                .iter()
+                .map(|&candidate| pattern::score(&ids, &table.secrets[candidate].ids))
+                .collect(),
+        }
+    }
+
+    /// Keeps only the candidates that would have given those colors to the guess
+    pub fn update(&mut self, guess: &[Letter], pattern: &[CharStatus]) {
+        let target = pattern::encode(pattern);
+        let mut patterns = self.patterns(guess).into_iter();
+        self.candidates.retain(|_| patterns.next() == Some(target));
+    }
+
+    /// Like [`Solver::update`], but exactly one of the colors is a lie, like in Fibble: keeps only the
+    /// candidates that would have given the guess those colors except for one letter
+    pub fn update_with_lie(&mut self, guess: &[Letter], pattern: &[CharStatus]) {
+        let shown = pattern::encode(pattern);
+        let mut patterns = self.patterns(guess).into_iter();
+        self.candidates.retain(|_| {
+            patterns
+                .next()
+                .is_some_and(|pattern| pattern::distance(pattern, shown) == 1)
+        });
+    }
+
+    /// Like [`Solver::update`], but the colors may also come from another secret word, like in Xordle:
+    /// keeps only the candidates that would not have given any letter of the guess a better color
+    pub fn update_within(&mut self, guess: &[Letter], pattern: &[CharStatus]) {
+        let bound = pattern::encode(pattern);
+        let mut patterns = self.patterns(guess).into_iter();
+        self.candidates
+            .retain(|_| patterns.next().is_some_and(|pattern| pattern::within(pattern, bound)));
+    }
+
+    /// How many candidates would give the guess each pattern, only for the patterns some candidate gives
+    pub(crate) fn partition(&self, guess: &[Letter]) -> HashMap<Pattern, usize> {
+        let mut sizes: HashMap<Pattern, usize> = HashMap::new();
+        for pattern in self.patterns(guess) {
+            *sizes.entry(pattern).or_default() += 1;
+        }
+        sizes
+    }
+
+    /// Keeps only the guesses that can be played, like the ones that respect the hints in hard difficulty
+    pub fn retain_guesses(&mut self, mut f: impl FnMut(&[Letter]) -> bool) {
+        let table = &self.table;
+        self.guesses.retain(|&guess| f(&table.guesses[guess].letters));
+    }
+
+    /// Secret words still possible, sorted
+    pub fn candidates(&self) -> Vec<String> {
+        self.candidates
+            .iter()
+            .map(|&candidate| self.table.secrets[candidate].text())
+            .collect()
+    }
+
+    /// Indexes of the secret words still possible, see [`Solver::secret_word`]
+    pub fn candidate_indexes(&self) -> &[usize] {
+        &self.candidates
+    }
+
+    /// Indexes of the words that can be guessed, see [`Solver::guess_word`]
+    pub fn guess_indexes(&self) -> &[usize] {
+        &self.guesses
+    }
+
+    /// The word that can be guessed with the index
+    pub fn guess_word(&self, guess: usize) -> &Word {
+        &self.table.guesses[guess]
+    }
+
+    /// The secret word with the index
+    pub fn secret_word(&self, secret: usize) -> &Word {
+        &self.table.secrets[secret]
+    }
+
+    /// For each word that can be guessed, by its index, whether it could be the secret word
+    pub fn candidate_guesses(&self) -> Vec<bool> {
+        let mut candidate = vec![false; self.table.guesses.len()];
+        for &secret in self.candidates.iter() {
+            if let Some(guess) = self.table.secret_guess[secret] {
+                candidate[guess] = true;
+            }
+        }
+        candidate
+    }
+
+    /// How many candidates would give the guess each pattern, only for the patterns some candidate gives
+    pub fn group_sizes(&self, guess: usize) -> Vec<usize> {
+        let patterns = self
+            .candidates
+            .iter()
+            .map(|&candidate| self.table.matrix.get(guess, candidate));
+        if self.table.length <= DENSE_LENGTH {
+            let mut sizes = vec![0; pattern::count(self.table.length)];
+            patterns.for_each(|pattern| sizes[pattern as usize] += 1);
+            sizes.retain(|&size| size > 0);
+            sizes
+        } else {
+            let mut sizes: HashMap<Pattern, usize> = HashMap::new();
+            patterns.for_each(|pattern| *sizes.entry(pattern).or_default() += 1);
+            sizes.into_values().collect()
+        }
+    }
+
+    /// Expected information of the guess in bits: how the candidates are split by the colors they would give it
+    pub fn entropy(&self, guess: usize) -> f64 {
+        let total = self.candidates.len() as f64;
+        self.group_sizes(guess)
+            .into_iter()
+            .map(|size| {
+                let p = size as f64 / total;
+                p * (1.0 / p).log2()
+            })
+            .sum()
+    }
+
+    /// The best guesses, the ones expected to reveal the most. Between guesses that reveal the same,
+    /// candidates go first since they could win. Guesses that reveal nothing and can't win are left out
+    pub fn suggest(&self, count: usize) -> Vec<Suggestion> {
+        let candidate = self.candidate_guesses();
+        let mut suggestions: Vec<Suggestion> = self
+            .guesses
+            .par_iter()
+            .map(|&guess| Suggestion {
+                word: self.table.guesses[guess].text(),
+                entropy: self.entropy(guess),
+                candidate: candidate[guess],
+            })
+            .filter(|suggestion| suggestion.candidate || suggestion.entropy > 0.0)
+            .collect();
+        suggestions.sort_by(|a, b| {
+            b.entropy
+                .total_cmp(&a.entropy)
+                .then(b.candidate.cmp(&a.candidate))
+                .then(a.word.cmp(&b.word))
+        });
+        suggestions.truncate(count);
+        suggestions
+    }
+
+    /// The candidates and the best guesses
+    pub fn suggestions(&self, count: usize) -> Suggestions {
+        Suggestions {
+            candidates: self.candidates(),
+            guesses: self.suggest(count),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii::Folding;
+
+    fn cached_files(dir: &Path) -> Vec<String> {
+        let mut files: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn the_cache_keeps_the_last_matrix_of_each_length() {
+        let dir = std::env::temp_dir().join(format!("another_wordle_clone-cache-{}", std::process::id()));
+        let cached = |dictionary: &[u8], secret_words: &[u8], length: usize| {
+            let dictionary = Dictionary::new(dictionary, secret_words, Folding::default()).with_cache_dir(dir.clone());
+            Solver::new(&dictionary, length).unwrap();
+            cached_files(&dir)
+        };
+
+        let first = cached(b"crane\nslate\n", b"crane\n", 5);
+        let again = cached(b"crane\nslate\n", b"crane\n", 5);
+        let other_words = cached(b"crane\nslate\nmoist\n", b"crane\n", 5);
+        let other_length = cached(b"crane\nslate\nmoist\n", b"cran\n", 4);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(again, first);
+        assert_eq!(other_words.len(), 1);
+        assert_ne!(other_words, first);
+        assert_eq!(other_length.len(), 2);
+        // The name only depends on the words, so it is the same in every release and on every computer
+        assert_eq!(first, ["patterns-5-d7e94389cc50e3ed.bin"]);
+    }
+}
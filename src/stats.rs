@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use json::JsonValue;
+
+/// Statistics of the games played in one language with one word length and number of boards
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Games finished, won or lost
+    pub played: u32,
+    /// Games won
+    pub wins: u32,
+    /// Games won in a row up to the last one
+    pub current_streak: u32,
+    /// Most games ever won in a row
+    pub max_streak: u32,
+    /// How many games were won with each number of guesses, the first one is for games won in one guess
+    pub guesses: Vec<u32>,
+}
+
+impl Stats {
+    /// Counts a game won with that many tries
+    pub fn record_win(&mut self, tries: u8) {
+        self.played += 1;
+        self.wins += 1;
+        self.current_streak += 1;
+        self.max_streak = self.max_streak.max(self.current_streak);
+
+        let index = tries.max(1) as usize - 1;
+        if self.guesses.len() <= index {
+            self.guesses.resize(index + 1, 0);
+        }
+        self.guesses[index] += 1;
+    }
+
+    /// Counts a game lost, which ends the streak
+    pub fn record_loss(&mut self) {
+        self.played += 1;
+        self.current_streak = 0;
+    }
+
+    /// Percentage of the games played that were won, 0 if none was played
+    pub fn win_rate(&self) -> u32 {
+        if self.played == 0 {
+            return 0;
+        }
+        (self.wins as f64 * 100.0 / self.played as f64).round() as u32
+    }
+
+    /// The statistics as they are saved and as the json output shows them
+    pub fn to_json(&self) -> JsonValue {
+        json::object! {
+            played: self.played,
+            wins: self.wins,
+            current_streak: self.current_streak,
+            max_streak: self.max_streak,
+            guesses: self.guesses.clone(),
+        }
+    }
+
+    fn from_json(value: &JsonValue) -> Self {
+        Self {
+            played: value["played"].as_u32().unwrap_or_default(),
+            wins: value["wins"].as_u32().unwrap_or_default(),
+            current_streak: value["current_streak"].as_u32().unwrap_or_default(),
+            max_streak: value["max_streak"].as_u32().unwrap_or_default(),
+            guesses: value["guesses"]
+                .members()
+                .map(|n| n.as_u32().unwrap_or_default())
+                .collect(),
+        }
+    }
+}
+
+/// Statistics of every language, word length and number of boards played, saved as a json file
+pub struct StatsStore {
+    path: Option<PathBuf>,
+    stats: BTreeMap<String, Stats>,
+}
+
+fn key(language: &str, length: usize, boards: usize) -> String {
+    if boards == 1 {
+        format!("{}-{}", language, length)
+    } else {
+        format!("{}-{}x{}", language, length, boards)
+    }
+}
+
+impl StatsStore {
+    /// Reads the statistics saved in the file. If there is no file or it can't be read, statistics start from zero
+    pub fn load(path: &Path) -> Self {
+        let stats = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|text| json::parse(&text).ok())
+            .map(|value| {
+                value
+                    .entries()
+                    .map(|(key, stats)| (key.to_owned(), Stats::from_json(stats)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            path: Some(path.to_owned()),
+            stats,
+        }
+    }
+
+    /// Statistics that are never saved, for when there is nowhere to save them
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            stats: BTreeMap::new(),
+        }
+    }
+
+    /// Writes all the statistics to their file, nothing if they are only kept in memory
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut value = JsonValue::new_object();
+        for (key, stats) in self.stats.iter() {
+            value[key.as_str()] = stats.to_json();
+        }
+        std::fs::write(path, value.pretty(2))
+    }
+
+    /// A copy of the statistics of those games, empty if none was played
+    pub fn get(&self, language: &str, length: usize, boards: usize) -> Stats {
+        self.stats
+            .get(&key(language, length, boards))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The statistics of those games to record one, added if none was played
+    pub fn get_mut(&mut self, language: &str, length: usize, boards: usize) -> &mut Stats {
+        self.stats.entry(key(language, length, boards)).or_default()
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::seq::SliceRandom;
+use rayon::prelude::*;
+
+use crate::char::Letter;
+use crate::random::{self, GameRng};
+use crate::solver::{Solver, Word};
+
+/// Names of the strategies, to choose them with [`from_name`]
+pub const STRATEGIES: [&str; 4] = ["entropy", "minimax", "frequency", "random"];
+
+/// A way of choosing the next guess from what the solver knows. It can be implemented outside the crate
+/// with the words and scores the solver gives:
+///
+/// ```
+/// use another_wordle_clone::char::Letter;
+/// use another_wordle_clone::solver::Solver;
+/// use another_wordle_clone::strategy::Strategy;
+///
+/// /// Always guesses the first secret word still possible
+/// struct FirstCandidate;
+///
+/// impl Strategy for FirstCandidate {
+///     fn name(&self) -> &'static str {
+///         "first"
+///     }
+///
+///     fn next_guess(&mut self, solver: &Solver) -> Option<Vec<Letter>> {
+///         let &candidate = solver.candidate_indexes().first()?;
+///         Some(solver.secret_word(candidate).letters.clone())
+///     }
+/// }
+/// ```
+pub trait Strategy {
+    /// Name to choose the strategy by, like "entropy"
+    fn name(&self) -> &'static str;
+
+    /// The next guess, none if no candidate is left
+    fn next_guess(&mut self, solver: &Solver) -> Option<Vec<Letter>>;
+}
+
+/// The strategy with that name, the random one chooses with the seed
+pub fn from_name(name: &str, seed: u64) -> Option<Box<dyn Strategy>> {
+    match name {
+        "entropy" => Some(Box::new(Entropy)),
+        "minimax" => Some(Box::new(Minimax)),
+        "frequency" => Some(Box::new(LetterFrequency)),
+        "random" => Some(Box::new(RandomConsistent::new(seed))),
+        _ => None,
+    }
+}
+
+/// The guess with the highest value. Between guesses with the same value candidates go first, since they
+/// could win, and then the first one in alphabetical order
+fn best_guess(solver: &Solver, value: impl Fn(usize) -> f64 + Sync) -> Option<Vec<Letter>> {
+    if solver.candidate_indexes().is_empty() {
+        return None;
+    }
+    let candidate = solver.candidate_guesses();
+    solver
+        .guess_indexes()
+        .par_iter()
+        .map(|&guess| (value(guess), candidate[guess], solver.guess_word(guess)))
+        .max_by(|a, b| {
+            a.0.total_cmp(&b.0)
+                .then(a.1.cmp(&b.1))
+                .then(b.2.letters.cmp(&a.2.letters))
+        })
+        .map(|(_, _, word)| word.letters.clone())
+}
+
+/// The guess expected to reveal the most information, the same one the "s" command suggests first
+pub struct Entropy;
+
+impl Strategy for Entropy {
+    fn name(&self) -> &'static str {
+        "entropy"
+    }
+
+    fn next_guess(&mut self, solver: &Solver) -> Option<Vec<Letter>> {
+        best_guess(solver, |guess| solver.entropy(guess))
+    }
+}
+
+/// The guess that leaves the fewest candidates in the worst case, whatever colors it gets
+pub struct Minimax;
+
+impl Strategy for Minimax {
+    fn name(&self) -> &'static str {
+        "minimax"
+    }
+
+    fn next_guess(&mut self, solver: &Solver) -> Option<Vec<Letter>> {
+        best_guess(solver, |guess| {
+            -(solver.group_sizes(guess).into_iter().max().unwrap_or_default() as f64)
+        })
+    }
+}
+
+/// The candidate whose different letters are in the most candidates, without looking at the colors
+pub struct LetterFrequency;
+
+impl Strategy for LetterFrequency {
+    fn name(&self) -> &'static str {
+        "frequency"
+    }
+
+    fn next_guess(&mut self, solver: &Solver) -> Option<Vec<Letter>> {
+        let candidates: Vec<&Word> = solver
+            .candidate_indexes()
+            .iter()
+            .map(|&candidate| solver.secret_word(candidate))
+            .collect();
+        let mut frequencies: HashMap<u16, usize> = HashMap::new();
+        for candidate in candidates.iter() {
+            let ids: HashSet<u16> = candidate.ids.iter().copied().collect();
+            for id in ids {
+                *frequencies.entry(id).or_default() += 1;
+            }
+        }
+        // The first one in alphabetical order between the ones with the same frequency
+        candidates
+            .iter()
+            .max_by_key(|candidate| {
+                let ids: HashSet<u16> = candidate.ids.iter().copied().collect();
+                let frequency: usize = ids.iter().map(|id| frequencies[id]).sum();
+                (frequency, std::cmp::Reverse(&candidate.letters))
+            })
+            .map(|candidate| candidate.letters.clone())
+    }
+}
+
+/// Any of the candidates, chosen at random
+pub struct RandomConsistent {
+    rng: GameRng,
+}
+
+impl RandomConsistent {
+    /// Chooses with the seed, so the same seed always plays the same games
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: random::from_seed(seed),
+        }
+    }
+}
+
+impl Strategy for RandomConsistent {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    fn next_guess(&mut self, solver: &Solver) -> Option<Vec<Letter>> {
+        solver
+            .candidate_indexes()
+            .choose(&mut self.rng)
+            .map(|&candidate| solver.secret_word(candidate).letters.clone())
+    }
+}
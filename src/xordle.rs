@@ -0,0 +1,293 @@
+use crate::char::{letters, Char, CharStatus, Letter};
+use crate::dictionary::Dictionary;
+use crate::multi::{Board, MultiEvent, MultiSession};
+use crate::session::{Command, InvalidGuess, Session};
+use crate::solver::{Solver, Suggestions, SUGGESTIONS};
+
+/// The status that reveals the most, correct being the best and incorrect the worst
+fn best(a: &CharStatus, b: &CharStatus) -> CharStatus {
+    match (a, b) {
+        (CharStatus::Correct, _) | (_, CharStatus::Correct) => CharStatus::Correct,
+        (CharStatus::Misplaced, _) | (_, CharStatus::Misplaced) => CharStatus::Misplaced,
+        _ => CharStatus::Incorrect,
+    }
+}
+
+/// The rows merged in a single one: each letter gets the best status it got in any of them
+fn merge(rows: &[&[Char]]) -> Vec<Char> {
+    let mut merged = rows[0].to_vec();
+    for row in &rows[1..] {
+        for (c, other) in merged.iter_mut().zip(row.iter()) {
+            let status = best(&c.status, &other.status);
+            c.set_status(status);
+        }
+    }
+    merged
+}
+
+/// A game with two secret words that share no letter, like Xordle: every guess is compared against both
+/// and their colors are shown merged on a single board. It's played on the boards of a [`MultiSession`],
+/// one for each secret word, so a secret word found is not compared anymore and the game is won when both
+/// are found. Its events are the ones of that session with the rows merged in one
+pub struct XordleSession<'a> {
+    dictionary: &'a Dictionary,
+    /// The game on both boards, the player only sees them merged
+    multi: MultiSession<'a>,
+    /// Every guess with the colors of the secret words not found before it merged
+    history: Vec<Vec<Char>>,
+}
+
+impl<'a> XordleSession<'a> {
+    /// The secret words must have the same length and share no letter, like the ones of
+    /// [`Dictionary::get_disjoint_secret_words`]
+    pub fn new(dictionary: &'a Dictionary, secret_words: &[Vec<Letter>; 2], max_tries: u8) -> Self {
+        Self {
+            dictionary,
+            multi: MultiSession::new(dictionary, secret_words, max_tries),
+            history: Vec::new(),
+        }
+    }
+
+    /// The abecedary of both secret words merged: each letter has the best status it got in any of them
+    pub fn abecedary(&self) -> Vec<Char> {
+        let abecedaries: Vec<Vec<Char>> = self
+            .boards()
+            .iter()
+            .map(|board| board.feedback.get_abecedary())
+            .collect();
+        // Both are sorted the same and have the same letters, only their status changes
+        abecedaries[0]
+            .iter()
+            .zip(abecedaries[1].iter())
+            .map(|(a, b)| match (a.is_revealed(), b.is_revealed()) {
+                (true, true) => {
+                    let mut c = a.clone();
+                    c.set_status(best(&a.status, &b.status));
+                    c
+                }
+                (false, true) => b.clone(),
+                _ => a.clone(),
+            })
+            .collect()
+    }
+
+    /// Suggestions for the secret words not found yet. Each merged row is the best the candidates could have
+    /// got, and the rows after a secret word was found have exactly the colors of the other one. The row that
+    /// found a secret word tells the other one shares no letter with it, which also leaves the found one out
+    fn suggest(&self) -> Suggestions {
+        let Some(mut solver) = Solver::new(self.dictionary, self.length()) else {
+            return Suggestions::default();
+        };
+        for (i, row) in self.history.iter().enumerate() {
+            let guess: Vec<Letter> = row.iter().map(|c| c.character.clone()).collect();
+            let pattern: Vec<CharStatus> = row.iter().map(|c| c.status.clone()).collect();
+            // Tries start at 1, so the row i was scored in the try i + 1
+            let solved_at = |board: &Board| board.solved_at.map(|try_index| try_index as usize);
+            if self
+                .boards()
+                .iter()
+                .any(|board| solved_at(board).is_some_and(|try_index| try_index <= i))
+            {
+                solver.update(&guess, &pattern);
+            } else if self.boards().iter().any(|board| solved_at(board) == Some(i + 1)) {
+                solver.update(&guess, &vec![CharStatus::Incorrect; guess.len()]);
+            } else {
+                solver.update_within(&guess, &pattern);
+            }
+        }
+        solver.suggestions(SUGGESTIONS)
+    }
+
+    /// Checks the guess could be played, without comparing it to the secret words. A secret word already
+    /// found can't be guessed again, it would only spend a try
+    pub fn validate(&self, guess: &str) -> Result<(), InvalidGuess> {
+        self.multi.validate(guess)?;
+        let guess = letters(guess);
+        let found = self.boards().iter().filter(|board| board.is_solved()).any(|board| {
+            let secret = letters(&board.feedback.get_secret());
+            guess.len() == secret.len()
+                && guess
+                    .iter()
+                    .zip(secret.iter())
+                    .all(|(a, b)| self.dictionary.folding.compare_letters(a, b))
+        });
+        if found {
+            return Err(InvalidGuess::AlreadyFound);
+        }
+        Ok(())
+    }
+
+    // Getters
+
+    /// Every guess with the colors of the secret words merged
+    pub fn history(&self) -> &[Vec<Char>] {
+        &self.history
+    }
+
+    /// One board for each secret word, in the order they were given
+    pub fn boards(&self) -> &[Board] {
+        self.multi.boards()
+    }
+
+    /// Letters of the secret words
+    pub fn length(&self) -> usize {
+        self.multi.length()
+    }
+
+    /// Guesses scored so far
+    pub fn tries(&self) -> u8 {
+        self.multi.tries()
+    }
+
+    /// Guesses that can be scored before the game is lost
+    pub fn max_tries(&self) -> u8 {
+        self.multi.max_tries()
+    }
+
+    /// Guesses that can still be scored
+    pub fn tries_left(&self) -> u8 {
+        self.multi.tries_left()
+    }
+
+    /// Checks if both secret words were guessed
+    pub fn is_won(&self) -> bool {
+        self.multi.is_won()
+    }
+
+    /// Checks if the tries ran out before guessing both secret words
+    pub fn is_lost(&self) -> bool {
+        self.multi.is_lost()
+    }
+
+    /// Checks if the game is won, lost or quit
+    pub fn is_over(&self) -> bool {
+        self.multi.is_over()
+    }
+}
+
+impl Session for XordleSession<'_> {
+    type Event = MultiEvent;
+
+    fn command(&mut self, command: Command) -> Self::Event {
+        match command {
+            Command::PreviousWord => MultiEvent::PreviousWord(self.history.last().map(|row| vec![Some(row.clone())])),
+            Command::History => MultiEvent::History(vec![self.history.clone()]),
+            Command::Abecedary => MultiEvent::Abecedary(vec![self.abecedary()]),
+            Command::Suggest => MultiEvent::Suggestions(vec![Some(self.suggest())]),
+            Command::TriesLeft | Command::Statistics | Command::Help | Command::Quit => self.multi.command(command),
+        }
+    }
+
+    /// Compares the guess to the secret words not found yet, see [`MultiSession`]. Its rows are merged in the
+    /// one the player sees, with the colors of the secret words not found before it
+    fn guess(&mut self, guess: &str) -> Vec<Self::Event> {
+        if self.is_over() {
+            return vec![MultiEvent::InvalidGuess(InvalidGuess::GameOver)];
+        }
+        if let Err(reason) = self.validate(guess) {
+            return vec![MultiEvent::InvalidGuess(reason)];
+        }
+        self.multi
+            .guess(guess)
+            .into_iter()
+            .map(|event| match event {
+                MultiEvent::Scored(rows) => {
+                    let rows: Vec<&[Char]> = rows.iter().flatten().map(Vec::as_slice).collect();
+                    let row = merge(&rows);
+                    self.history.push(row.clone());
+                    MultiEvent::Scored(vec![Some(row)])
+                }
+                event => event,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii::Folding;
+
+    fn dictionary() -> Dictionary {
+        Dictionary::new(b"piano\ntrain\nstink\n", b"mango\nbrisk\n", Folding::default())
+    }
+
+    fn session(dictionary: &Dictionary) -> XordleSession<'_> {
+        XordleSession::new(dictionary, &[letters("mango"), letters("brisk")], 7)
+    }
+
+    fn row(word: &str, statuses: &[CharStatus]) -> Vec<Char> {
+        letters(word)
+            .into_iter()
+            .zip(statuses)
+            .map(|(letter, status)| {
+                let mut c = Char::new(letter);
+                c.set_status(status.clone());
+                c
+            })
+            .collect()
+    }
+
+    fn statuses(row: &[Char]) -> Vec<CharStatus> {
+        row.iter().map(|c| c.status.clone()).collect()
+    }
+
+    #[test]
+    fn merging_keeps_the_best_status_of_each_letter() {
+        use CharStatus::*;
+        let mango = row("piano", &[Incorrect, Incorrect, Misplaced, Misplaced, Correct]);
+        let brisk = row("piano", &[Incorrect, Misplaced, Incorrect, Incorrect, Incorrect]);
+        let merged = merge(&[&mango, &brisk]);
+        assert_eq!(statuses(&merged), [Incorrect, Misplaced, Misplaced, Misplaced, Correct]);
+        assert_eq!(
+            merged.iter().map(|c| c.character.clone()).collect::<Vec<_>>(),
+            letters("piano")
+        );
+    }
+
+    #[test]
+    fn guesses_show_both_secret_words_merged_in_one_row() {
+        use CharStatus::*;
+        let dictionary = dictionary();
+        let mut session = session(&dictionary);
+        let events = session.input("piano");
+        let MultiEvent::Scored(rows) = &events[0] else {
+            panic!("the guess was not scored: {:?}", events);
+        };
+        let [Some(row)] = &rows[..] else {
+            panic!("the guess was not scored in a single row: {:?}", rows);
+        };
+        assert_eq!(statuses(row), [Incorrect, Misplaced, Misplaced, Misplaced, Correct]);
+        assert_eq!(session.history(), std::slice::from_ref(row));
+    }
+
+    #[test]
+    fn a_secret_word_found_can_not_be_guessed_again() {
+        let dictionary = dictionary();
+        let mut session = session(&dictionary);
+        session.input("mango");
+        assert_eq!(session.tries(), 1);
+        assert!(!session.is_won());
+
+        let events = session.input("mango");
+        assert_eq!(events, [MultiEvent::InvalidGuess(InvalidGuess::AlreadyFound)]);
+        assert_eq!(session.tries(), 1);
+        assert_eq!(session.history().len(), 1);
+    }
+
+    #[test]
+    fn suggestions_leave_out_the_secret_word_found() {
+        let dictionary = dictionary();
+        let mut session = session(&dictionary);
+        session.input("piano");
+        session.input("mango");
+        let events = session.input("s");
+        let [MultiEvent::Suggestions(boards)] = &events[..] else {
+            panic!("no suggestions: {:?}", events);
+        };
+        let [Some(suggestions)] = &boards[..] else {
+            panic!("the suggestions are not for a single board: {:?}", boards);
+        };
+        assert_eq!(suggestions.candidates, ["brisk"]);
+    }
+}